@@ -0,0 +1,442 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+const BASE_BITS: u32 = 32;
+
+/// Cele cislo s libovolnou presnosti. Cislice jsou ulozene od nejnizsi
+/// v soustave o zakladu 2^32, nula ma prazdny vektor a neni zaporna.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    digits: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        BigInt {
+            negative: false,
+            digits: Vec::new(),
+        }
+    }
+
+    fn from_parts(negative: bool, digits: Vec<u32>) -> Self {
+        let mut number = BigInt { negative, digits };
+        number.trim();
+        number
+    }
+
+    fn trim(&mut self) {
+        while let Some(&0) = self.digits.last() {
+            self.digits.pop();
+        }
+        if self.digits.is_empty() {
+            self.negative = false;
+        }
+    }
+
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        let value = value.trunc();
+        let negative = value < 0.0;
+        let mut magnitude = value.abs();
+        let mut digits = Vec::new();
+        while magnitude >= 1.0 {
+            let digit = magnitude % 4294967296.0;
+            digits.push(digit as u32);
+            magnitude = ((magnitude - digit) / 4294967296.0).trunc();
+        }
+        Some(BigInt::from_parts(negative, digits))
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+        if text.is_empty() || !text.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let mut digits: Vec<u32> = Vec::new();
+        for c in text.chars() {
+            let mut carry = c.to_digit(10).unwrap() as u64;
+            for digit in digits.iter_mut() {
+                let value = *digit as u64 * 10 + carry;
+                *digit = value as u32;
+                carry = value >> BASE_BITS;
+            }
+            if carry > 0 {
+                digits.push(carry as u32);
+            }
+        }
+        Some(BigInt::from_parts(negative, digits))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// Pocet bitu absolutni hodnoty, nula jich ma 0.
+    pub fn bits(&self) -> u64 {
+        match self.digits.last() {
+            Some(top) => (self.digits.len() as u64 - 1) * BASE_BITS as u64 + (BASE_BITS - top.leading_zeros()) as u64,
+            None => 0,
+        }
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        if self.digits.len() > 2 {
+            return None;
+        }
+        let mut magnitude: u64 = 0;
+        for (i, digit) in self.digits.iter().enumerate() {
+            magnitude |= (*digit as u64) << (BASE_BITS * i as u32);
+        }
+        if self.negative {
+            if magnitude <= i64::MAX as u64 + 1 {
+                Some((magnitude as i64).wrapping_neg())
+            } else {
+                None
+            }
+        } else if magnitude <= i64::MAX as u64 {
+            Some(magnitude as i64)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        let mut value = 0.0;
+        for digit in self.digits.iter().rev() {
+            value = value * 4294967296.0 + *digit as f64;
+        }
+        if self.negative {
+            -value
+        } else {
+            value
+        }
+    }
+
+    pub fn neg(&self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.digits.clone())
+    }
+
+    pub fn add(&self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(self.negative, add_magnitudes(&self.digits, &other.digits));
+        }
+        match compare_magnitudes(&self.digits, &other.digits) {
+            Ordering::Less => BigInt::from_parts(
+                other.negative,
+                sub_magnitudes(&other.digits, &self.digits),
+            ),
+            _ => BigInt::from_parts(self.negative, sub_magnitudes(&self.digits, &other.digits)),
+        }
+    }
+
+    pub fn sub(&self, other: &BigInt) -> BigInt {
+        self.add(&other.neg())
+    }
+
+    pub fn mul(&self, other: &BigInt) -> BigInt {
+        if self.is_zero() || other.is_zero() {
+            return BigInt::zero();
+        }
+        let mut digits = vec![0u32; self.digits.len() + other.digits.len()];
+        for (i, a) in self.digits.iter().enumerate() {
+            let mut carry: u64 = 0;
+            for (j, b) in other.digits.iter().enumerate() {
+                let value = digits[i + j] as u64 + (*a as u64) * (*b as u64) + carry;
+                digits[i + j] = value as u32;
+                carry = value >> BASE_BITS;
+            }
+            let mut k = i + other.digits.len();
+            while carry > 0 {
+                let value = digits[k] as u64 + carry;
+                digits[k] = value as u32;
+                carry = value >> BASE_BITS;
+                k += 1;
+            }
+        }
+        BigInt::from_parts(self.negative != other.negative, digits)
+    }
+
+    /// Deleni zaokrouhlene dolu, zbytek ma stejne znamenko jako delitel.
+    /// Pro nulovy delitel vraci `None`.
+    pub fn div_mod_floor(&self, other: &BigInt) -> Option<(BigInt, BigInt)> {
+        if other.is_zero() {
+            return None;
+        }
+        let (quotient, remainder) = div_rem_magnitudes(&self.digits, &other.digits);
+        let mut quotient = BigInt::from_parts(self.negative != other.negative, quotient);
+        let mut remainder = BigInt::from_parts(self.negative, remainder);
+        if !remainder.is_zero() && remainder.negative != other.negative {
+            quotient = quotient.sub(&BigInt::from(1));
+            remainder = remainder.add(other);
+        }
+        Some((quotient, remainder))
+    }
+
+    pub fn pow(&self, mut exponent: u64) -> BigInt {
+        let mut result = BigInt::from(1);
+        let mut base = self.clone();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.mul(&base);
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = base.mul(&base);
+            }
+        }
+        result
+    }
+
+    pub fn shl(&self, bits: usize) -> BigInt {
+        if self.is_zero() {
+            return BigInt::zero();
+        }
+        let mut digits = vec![0u32; bits / BASE_BITS as usize];
+        let shift = (bits % BASE_BITS as usize) as u32;
+        let mut carry = 0;
+        for digit in &self.digits {
+            if shift == 0 {
+                digits.push(*digit);
+            } else {
+                digits.push((*digit << shift) | carry);
+                carry = *digit >> (BASE_BITS - shift);
+            }
+        }
+        digits.push(carry);
+        BigInt::from_parts(self.negative, digits)
+    }
+
+    /// Aritmeticky posun doprava, zaokrouhluje dolu stejne jako u `i64`.
+    /// Posun doprava zaokrouhleny dolu jako u `i64`: zaporne cislo se
+    /// posouva ve dvojkovem doplnku a zleva se doplnuji jednicky.
+    pub fn shr(&self, bits: usize) -> BigInt {
+        let skip = bits / BASE_BITS as usize;
+        let length = self.digits.len() + 1;
+        if skip >= length {
+            return if self.negative { BigInt::from(-1) } else { BigInt::zero() };
+        }
+        let digits = self.twos_complement(length);
+        let fill = if self.negative { u32::MAX } else { 0 };
+        let shift = (bits % BASE_BITS as usize) as u32;
+        let shifted: Vec<u32> = (skip..length)
+            .map(|i| {
+                let high = digits.get(i + 1).copied().unwrap_or(fill);
+                if shift == 0 {
+                    digits[i]
+                } else {
+                    (digits[i] >> shift) | (high << (BASE_BITS - shift))
+                }
+            })
+            .collect();
+        if self.negative {
+            BigInt::from_parts(true, negate_digits(&shifted))
+        } else {
+            BigInt::from_parts(false, shifted)
+        }
+    }
+
+    pub fn not(&self) -> BigInt {
+        self.neg().sub(&BigInt::from(1))
+    }
+
+    pub fn and(&self, other: &BigInt) -> BigInt {
+        self.bitwise(other, |a, b| a & b)
+    }
+
+    pub fn or(&self, other: &BigInt) -> BigInt {
+        self.bitwise(other, |a, b| a | b)
+    }
+
+    pub fn xor(&self, other: &BigInt) -> BigInt {
+        self.bitwise(other, |a, b| a ^ b)
+    }
+
+    fn bitwise(&self, other: &BigInt, op: fn(u32, u32) -> u32) -> BigInt {
+        let length = self.digits.len().max(other.digits.len()) + 1;
+        let a = self.twos_complement(length);
+        let b = other.twos_complement(length);
+        let digits: Vec<u32> = a.iter().zip(b.iter()).map(|(x, y)| op(*x, *y)).collect();
+        if digits[length - 1] >> (BASE_BITS - 1) == 1 {
+            BigInt::from_parts(true, negate_digits(&digits))
+        } else {
+            BigInt::from_parts(false, digits)
+        }
+    }
+
+    fn twos_complement(&self, length: usize) -> Vec<u32> {
+        let mut digits = self.digits.clone();
+        digits.resize(length, 0);
+        if self.negative {
+            negate_digits(&digits)
+        } else {
+            digits
+        }
+    }
+
+    /// Vydeli cislo malym delitelem a vrati zbytek, pouziva se pri vypisu.
+    fn div_small(digits: &mut [u32], divisor: u32) -> u32 {
+        let mut remainder: u64 = 0;
+        for digit in digits.iter_mut().rev() {
+            let value = (remainder << BASE_BITS) | *digit as u64;
+            *digit = (value / divisor as u64) as u32;
+            remainder = value % divisor as u64;
+        }
+        remainder as u32
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        let mut magnitude = value.unsigned_abs();
+        let mut digits = Vec::new();
+        while magnitude > 0 {
+            digits.push(magnitude as u32);
+            magnitude >>= BASE_BITS;
+        }
+        BigInt::from_parts(value < 0, digits)
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_magnitudes(&self.digits, &other.digits),
+            (true, true) => compare_magnitudes(&other.digits, &self.digits),
+        }
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let mut digits = self.digits.clone();
+        let mut chunks: Vec<u32> = Vec::new();
+        while !digits.is_empty() {
+            chunks.push(BigInt::div_small(&mut digits, 1_000_000_000));
+            while let Some(&0) = digits.last() {
+                digits.pop();
+            }
+        }
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", chunks.last().unwrap())?;
+        for chunk in chunks.iter().rev().skip(1) {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+fn compare_magnitudes(a: &[u32], b: &[u32]) -> Ordering {
+    if a.len() != b.len() {
+        return a.len().cmp(&b.len());
+    }
+    for (x, y) in a.iter().rev().zip(b.iter().rev()) {
+        if x != y {
+            return x.cmp(y);
+        }
+    }
+    Ordering::Equal
+}
+
+fn add_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry: u64 = 0;
+    for i in 0..a.len().max(b.len()) {
+        let value = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        result.push(value as u32);
+        carry = value >> BASE_BITS;
+    }
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+    result
+}
+
+/// Predpoklada, ze `a` je v absolutni hodnote alespon `b`.
+fn sub_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow: i64 = 0;
+    for (i, digit) in a.iter().enumerate() {
+        let mut value = *digit as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        if value < 0 {
+            value += 1 << BASE_BITS;
+            borrow = 1;
+        } else {
+            borrow = 0;
+        }
+        result.push(value as u32);
+    }
+    result
+}
+
+fn div_rem_magnitudes(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if compare_magnitudes(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    if b.len() == 1 {
+        let mut quotient = a.to_vec();
+        let remainder = BigInt::div_small(&mut quotient, b[0]);
+        return (quotient, vec![remainder]);
+    }
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder: Vec<u32> = Vec::new();
+    for i in (0..a.len() * BASE_BITS as usize).rev() {
+        shift_left_one(&mut remainder);
+        if (a[i / BASE_BITS as usize] >> (i % BASE_BITS as usize)) & 1 == 1 {
+            if remainder.is_empty() {
+                remainder.push(1);
+            } else {
+                remainder[0] |= 1;
+            }
+        }
+        if compare_magnitudes(&remainder, b) != Ordering::Less {
+            remainder = sub_magnitudes(&remainder, b);
+            while let Some(&0) = remainder.last() {
+                remainder.pop();
+            }
+            quotient[i / BASE_BITS as usize] |= 1 << (i % BASE_BITS as usize);
+        }
+    }
+    (quotient, remainder)
+}
+
+fn negate_digits(digits: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(digits.len());
+    let mut carry = 1u64;
+    for digit in digits {
+        let value = (!*digit) as u64 + carry;
+        result.push(value as u32);
+        carry = value >> BASE_BITS;
+    }
+    result
+}
+
+fn shift_left_one(digits: &mut Vec<u32>) {
+    let mut carry = 0;
+    for digit in digits.iter_mut() {
+        let next = *digit >> (BASE_BITS - 1);
+        *digit = (*digit << 1) | carry;
+        carry = next;
+    }
+    if carry > 0 {
+        digits.push(carry);
+    }
+}
@@ -0,0 +1,777 @@
+use crate::expressions::{is_comparison, special_method, NoOp};
+use crate::patterns::MatchArm;
+use crate::statements::{Function, MethodKind, Statement, StatementKind};
+use crate::token::Token;
+use crate::tokentype::TokenType;
+use crate::types::{Annotation, Signature, Type};
+use crate::value::{check_arguments, Field, Parameter, Value};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Co kontrola vi o tride: typy poli z anotaci a signatury metod
+/// podle druhu (instancni, staticke, gettery a settery). Trait je
+/// pro kontrolu trida, ktera ma jen instancni metody.
+#[derive(Default)]
+struct ClassInfo {
+    record: bool,
+    super_class: Option<String>,
+    traits: Vec<String>,
+    fields: HashMap<String, Type>,
+    methods: HashMap<String, Rc<Signature>>,
+    statics: HashMap<String, Rc<Signature>>,
+    getters: HashMap<String, Rc<Signature>>,
+    setters: HashMap<String, Rc<Signature>>,
+}
+
+impl ClassInfo {
+    fn members(&self, kind: MethodKind) -> &HashMap<String, Rc<Signature>> {
+        match kind {
+            MethodKind::Instance => &self.methods,
+            MethodKind::Static => &self.statics,
+            MethodKind::Getter => &self.getters,
+            MethodKind::Setter => &self.setters,
+        }
+    }
+
+    fn members_mut(&mut self, kind: MethodKind) -> &mut HashMap<String, Rc<Signature>> {
+        match kind {
+            MethodKind::Instance => &mut self.methods,
+            MethodKind::Static => &mut self.statics,
+            MethodKind::Getter => &mut self.getters,
+            MethodKind::Setter => &mut self.setters,
+        }
+    }
+}
+
+/// Postupna kontrola typu pro `moon check`. Prochazi strom z parseru
+/// (`Statement::check` a `Expr::check`) a hlasi jen to, co by za behu
+/// urcite selhalo. Promenne bez anotace maji typ `Any` a proti nim se nic
+/// nehlasi, takze kod bez anotaci projde beze zmeny.
+pub struct Checker {
+    scopes: Vec<HashMap<String, Type>>,
+    classes: HashMap<String, ClassInfo>,
+    returns: Vec<(Token, Type)>,
+    this_class: Vec<String>,
+    errors: Vec<(Token, String)>,
+}
+
+impl Checker {
+    pub fn new() -> Self {
+        Checker {
+            scopes: vec![HashMap::new()],
+            classes: HashMap::new(),
+            returns: Vec::new(),
+            this_class: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn check(mut self, statements: &[Rc<dyn Statement>]) -> Vec<(Token, String)> {
+        for statement in statements {
+            statement.check(&mut self);
+        }
+        self.errors.sort_by_key(|(token, _)| token.line);
+        self.errors
+    }
+
+    pub(crate) fn error(&mut self, msg: String, token: &Token) {
+        self.errors.push((token.clone(), msg));
+    }
+
+    pub(crate) fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    pub(crate) fn end_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    pub(crate) fn declare(&mut self, name: &str, ty: Type) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), ty);
+        }
+    }
+
+    /// Typ promenne. Jmena, o kterych kontrola nic nevi, maji typ `Any`.
+    pub(crate) fn lookup(&self, name: &str) -> Type {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .cloned()
+            .unwrap_or(Type::Any)
+    }
+
+    /// Prevede anotaci na typ a overi, ze trida z anotace existuje.
+    pub(crate) fn annotation(&mut self, annotation: Option<&Annotation>) -> Type {
+        match annotation {
+            None => Type::Any,
+            Some(annotation) => {
+                if let Type::Instance(name) = annotation.ty.required() {
+                    if !self.classes.contains_key(name) {
+                        self.error(format!("Neznamy typ '{}'.", name), &annotation.token);
+                        return Type::Any;
+                    }
+                }
+                annotation.ty.clone()
+            }
+        }
+    }
+
+    /// Jestli hodnota typu `actual` muze byt tam, kde se ceka `expected`.
+    /// `Int` projde i jako `Float`, `Num?` projde i jako `Num`, protoze
+    /// kontrola nezna zuzeni typu po `x != nil`.
+    pub(crate) fn accepts(&self, expected: &Type, actual: &Type) -> bool {
+        match (expected, actual) {
+            (Type::Any, _) | (_, Type::Any) => true,
+            (Type::Optional(_), Type::Nil) => true,
+            (Type::Optional(expected), actual) => self.accepts(expected, actual.required()),
+            (expected, Type::Optional(actual)) => self.accepts(expected, actual),
+            (Type::Num, actual) => actual.is_numeric(),
+            (Type::Float, Type::Int) => true,
+            (Type::Instance(expected), Type::Instance(actual)) => self.is_subclass(actual, expected),
+            (expected, actual) => expected == actual,
+        }
+    }
+
+    fn is_subclass(&self, class: &str, ancestor: &str) -> bool {
+        if class == ancestor {
+            return true;
+        }
+        let info = match self.classes.get(class) {
+            Some(info) => info,
+            None => return false,
+        };
+        if info.traits.iter().any(|used| used == ancestor) {
+            return true;
+        }
+        match &info.super_class {
+            Some(super_class) => self.is_subclass(super_class, ancestor),
+            None => false,
+        }
+    }
+
+    pub(crate) fn expect(
+        &mut self,
+        expected: &Type,
+        actual: &Type,
+        token: &Token,
+        msg: impl FnOnce() -> String,
+    ) {
+        if !self.accepts(expected, actual) {
+            self.error(msg(), token);
+        }
+    }
+
+    /// Spolecny typ dvou vetvi (`a ? b : c`, `a ?? b`, vetve `match`).
+    pub(crate) fn unify(&self, a: &Type, b: &Type) -> Type {
+        match (a, b) {
+            (a, b) if a == b => a.clone(),
+            (Type::Any, _) | (_, Type::Any) => Type::Any,
+            (Type::Nil, Type::Optional(_)) => b.clone(),
+            (Type::Optional(_), Type::Nil) => a.clone(),
+            (Type::Nil, other) | (other, Type::Nil) => Type::Optional(Box::new(other.clone())),
+            (a, b) if a.is_numeric() && b.is_numeric() => Type::Num,
+            _ => Type::Any,
+        }
+    }
+
+    pub(crate) fn value_type(value: &Value) -> Type {
+        match value {
+            Value::Int(_) | Value::BigInt(_) => Type::Int,
+            Value::Number(_) => Type::Float,
+            Value::String(_) => Type::String,
+            Value::Bool(_) => Type::Bool,
+            Value::None => Type::Nil,
+            Value::List(_) => Type::List,
+            Value::Tuple(_) => Type::Tuple,
+            Value::Set(_) => Type::Set,
+            Value::Range(_) => Type::Range,
+            _ => Type::Any,
+        }
+    }
+
+    /// Typ vysledku binarni operace. Hlasi operandy, se kterymi by
+    /// `expressions::binary_operation` za behu skoncila chybou.
+    pub(crate) fn binary(&mut self, operator: &TokenType, token: &Token, left: &Type, right: &Type) -> Type {
+        if let Some((name, swap, _)) = special_method(operator) {
+            let (receiver, argument) = if swap { (right, left) } else { (left, right) };
+            if let Some(signature) = self.special(receiver, name) {
+                self.arguments(&signature, token, std::slice::from_ref(argument), &[]);
+                if is_comparison(operator) {
+                    return Type::Bool;
+                }
+                return signature.returns.clone();
+            }
+        }
+        let (a, b) = (left.required(), right.required());
+        let unknown = *a == Type::Any || *b == Type::Any;
+        let numbers = |a: &Type| a.is_numeric() || *a == Type::Any;
+        let sets = matches!((a, b), (Type::Set, Type::Set | Type::Any) | (Type::Any, Type::Set));
+        if sets && matches!(operator, TokenType::Pipe | TokenType::Ampersand | TokenType::Minus) {
+            return Type::Set;
+        }
+        let msg = match operator {
+            TokenType::EqualEqual | TokenType::BangEqual => return Type::Bool,
+            TokenType::In | TokenType::NotIn => match b {
+                Type::String if matches!(a, Type::String | Type::Any) => return Type::Bool,
+                Type::String => "V retezci lze hledat jen retezec.",
+                Type::List | Type::Tuple | Type::Set | Type::Range | Type::Any => return Type::Bool,
+                _ => "Operator 'in' lze pouzit jen na seznamy, ntice, mnoziny, retezce, rozsahy a instance s metodou __contains__.",
+            },
+            TokenType::Greater | TokenType::GreaterEqual | TokenType::Less | TokenType::LessEqual => {
+                if numbers(a) && numbers(b) {
+                    return Type::Bool;
+                }
+                "Lze porovnat jen 2 cisla."
+            }
+            TokenType::Plus => {
+                let strings = |a: &Type| *a == Type::String || *a == Type::Any;
+                if strings(a) && strings(b) && (*a == Type::String || *b == Type::String) {
+                    return Type::String;
+                }
+                if numbers(a) && numbers(b) {
+                    return arithmetic(a, b, unknown);
+                }
+                "Lze spojit/secist jen dva retezce/cisla."
+            }
+            TokenType::Minus | TokenType::Star | TokenType::Percent | TokenType::TildeSlash => {
+                if numbers(a) && numbers(b) {
+                    return arithmetic(a, b, unknown);
+                }
+                match operator {
+                    TokenType::Minus => "Lze odecist jen 2 cisla.",
+                    TokenType::Star => "Lze nasobit jen 2 cisla.",
+                    TokenType::Percent => "Zbytek po deleni lze spocitat jen u 2 cisel.",
+                    _ => "Lze delit jen 2 cisla.",
+                }
+            }
+            TokenType::Slash => {
+                if numbers(a) && numbers(b) {
+                    return Type::Float;
+                }
+                "Lze delit jen 2 cisla."
+            }
+            TokenType::StarStar => {
+                if numbers(a) && numbers(b) {
+                    return arithmetic(a, b, unknown);
+                }
+                "Umocnit lze jen 2 cisla."
+            }
+            _ => {
+                let integers = |a: &Type| matches!(a, Type::Int | Type::Num | Type::Any);
+                if integers(a) && integers(b) {
+                    return Type::Int;
+                }
+                "Bitove operace lze pouzit jen na cela cisla."
+            }
+        };
+        // Za `Any` muze byt instance se specialni metodou, o instanci bez ni
+        // rozhodne az behovy cas.
+        let overloadable = |a: &Type| matches!(a, Type::Any | Type::Instance(_));
+        if special_method(operator).is_some() && (overloadable(a) || overloadable(b)) {
+            return if is_comparison(operator) { Type::Bool } else { Type::Any };
+        }
+        self.error(format!("{} Nalezeno {} a {}.", msg, left, right), token);
+        Type::Any
+    }
+
+    pub(crate) fn unary(&mut self, operator: &Token, right: &Type) -> Type {
+        let operand = right.required();
+        if operator.token_type == TokenType::Minus {
+            if let Some(signature) = self.special(right, "__neg__") {
+                return signature.returns.clone();
+            }
+        }
+        match operator.token_type {
+            TokenType::Bang => Type::Bool,
+            TokenType::Minus if operand.is_numeric() || *operand == Type::Any => operand.clone(),
+            TokenType::Minus => {
+                self.error(format!("Jsou mozna jen zaporna cisla. Nalezeno {}.", right), operator);
+                Type::Any
+            }
+            _ if matches!(operand, Type::Int | Type::Num | Type::Any) => Type::Int,
+            _ => {
+                self.error(
+                    format!("Bitovou negaci lze pouzit jen na cela cisla. Nalezeno {}.", right),
+                    operator,
+                );
+                Type::Any
+            }
+        }
+    }
+
+    pub(crate) fn call(
+        &mut self,
+        callee: &Type,
+        token: &Token,
+        arguments: &[Type],
+        named: &[(Token, Type)],
+    ) -> Type {
+        if let Some(signature) = self.special(callee, "__call__") {
+            self.arguments(&signature, token, arguments, named);
+            return signature.returns.clone();
+        }
+        match callee.required() {
+            Type::Function(Some(signature)) => {
+                self.arguments(signature, token, arguments, named);
+                signature.returns.clone()
+            }
+            Type::Function(None) | Type::Any => Type::Any,
+            Type::Class(name) => {
+                match self.method(name, "init", MethodKind::Instance) {
+                    Some(signature) => self.arguments(&signature, token, arguments, named),
+                    None if !arguments.is_empty() || !named.is_empty() => self.error(
+                        format!(
+                            "Ocekavano 0 argumentu ale bylo zadano {}.",
+                            arguments.len() + named.len()
+                        ),
+                        token,
+                    ),
+                    None => {}
+                }
+                Type::Instance(name.clone())
+            }
+            other => {
+                self.error(format!("Lze volat jen funkce a tridy. Nalezeno {}.", other), token);
+                Type::Any
+            }
+        }
+    }
+
+    fn arguments(&mut self, signature: &Signature, token: &Token, arguments: &[Type], named: &[(Token, Type)]) {
+        let names: Vec<Token> = named.iter().map(|(name, _)| name.clone()).collect();
+        if let Err(msg) = check_arguments(&signature.parameters, arguments.len(), &names) {
+            self.error(msg, token);
+            return;
+        }
+        let positional = signature.parameters.iter().take_while(|parameter| !parameter.rest).count();
+        let mut given: Vec<(usize, &Type)> = arguments.iter().take(positional).enumerate().collect();
+        for (name, argument) in named {
+            if let Some(index) = signature.parameters.iter().position(|p| p.name.lexeme == name.lexeme) {
+                given.push((index, argument));
+            }
+        }
+        for (index, argument) in given {
+            let parameter = &signature.parameters[index].name.lexeme;
+            let expected = &signature.types[index];
+            self.expect(expected, argument, token, || {
+                format!(
+                    "Parametr '{}' funkce '{}' ma typ {}, ale argument je {}.",
+                    parameter, signature.name, expected, argument
+                )
+            });
+        }
+    }
+
+    fn method(&self, class: &str, name: &str, kind: MethodKind) -> Option<Rc<Signature>> {
+        let info = self.classes.get(class)?;
+        if let Some(signature) = info.members(kind).get(name) {
+            return Some(Rc::clone(signature));
+        }
+        if let Some(signature) = info.traits.iter().find_map(|used| self.method(used, name, kind)) {
+            return Some(signature);
+        }
+        self.method(info.super_class.as_ref()?, name, kind)
+    }
+
+    /// Specialni metoda (`__add__`, ...) tridy instance, pokud ji kontrola zna.
+    fn special(&self, object: &Type, name: &str) -> Option<Rc<Signature>> {
+        match object.required() {
+            Type::Instance(class) => self.method(class, name, MethodKind::Instance),
+            _ => None,
+        }
+    }
+
+    fn field(&self, class: &str, name: &str) -> Option<Type> {
+        let info = self.classes.get(class)?;
+        match info.fields.get(name) {
+            Some(ty) => Some(ty.clone()),
+            None => self.field(info.super_class.as_ref()?, name),
+        }
+    }
+
+    pub(crate) fn get_property(&mut self, object: &Type, name: &Token) -> Type {
+        match object.required() {
+            Type::Instance(class) => {
+                if let Some(signature) = self.method(class, &name.lexeme, MethodKind::Instance) {
+                    return Type::Function(Some(signature));
+                }
+                if let Some(getter) = self.method(class, &name.lexeme, MethodKind::Getter) {
+                    return getter.returns.clone();
+                }
+                self.field(class, &name.lexeme).unwrap_or(Type::Any)
+            }
+            Type::Class(class) if self.classes.contains_key(class) => {
+                match self.method(class, &name.lexeme, MethodKind::Static) {
+                    Some(signature) => Type::Function(Some(signature)),
+                    None => {
+                        self.error(
+                            format!("Trida {} nema statickou metodu '{}'.", class, name.lexeme),
+                            name,
+                        );
+                        Type::Any
+                    }
+                }
+            }
+            Type::Range => match &*name.lexeme {
+                "start" | "end" => Type::Optional(Box::new(Type::Int)),
+                "step" => Type::Int,
+                _ => Type::Any,
+            },
+            Type::Set => match &*name.lexeme {
+                "add" | "remove" | "contains" | "union" | "intersection" | "difference" => Type::Function(None),
+                _ => {
+                    self.error(format!("Mnozina nema vlastnost '{}'.", name.lexeme), name);
+                    Type::Any
+                }
+            },
+            Type::Any | Type::Class(_) | Type::Generator => Type::Any,
+            other => {
+                self.error(format!("Jen instance maji vlastnosti. Nalezeno {}.", other), name);
+                Type::Any
+            }
+        }
+    }
+
+    pub(crate) fn set_property(&mut self, object: &Type, name: &Token, value: &Type) {
+        match object.required() {
+            Type::Instance(class) => {
+                if self.classes.get(class).is_some_and(|info| info.record) {
+                    self.error(format!("Zaznam {} nelze menit.", class), name);
+                    return;
+                }
+                if let Some(setter) = self.method(class, &name.lexeme, MethodKind::Setter) {
+                    let expected = setter.types.first().cloned().unwrap_or(Type::Any);
+                    self.expect(&expected, value, name, || {
+                        format!(
+                            "Setter '{}' ma parametr typu {}, ale hodnota je {}.",
+                            name.lexeme, expected, value
+                        )
+                    });
+                    return;
+                }
+                if self.method(class, &name.lexeme, MethodKind::Getter).is_some() {
+                    self.error(format!("Vlastnost '{}' jde jen cist.", name.lexeme), name);
+                    return;
+                }
+                if let Some(expected) = self.field(class, &name.lexeme) {
+                    self.expect(&expected, value, name, || {
+                        format!("Pole '{}' ma typ {}, ale hodnota je {}.", name.lexeme, expected, value)
+                    });
+                }
+            }
+            Type::Any | Type::Class(_) => {}
+            other => self.error(format!("Jen instance maji pole. Nalezeno {}.", other), name),
+        }
+    }
+
+    pub(crate) fn index(&mut self, object: &Type, index: &Type, token: &Token) -> Type {
+        if let Some(signature) = self.special(object, "__getitem__") {
+            self.arguments(&signature, token, std::slice::from_ref(index), &[]);
+            return signature.returns.clone();
+        }
+        let slice = *index.required() == Type::Range;
+        let integer = matches!(index.required(), Type::Int | Type::Num | Type::Any);
+        match object.required() {
+            Type::List | Type::Tuple | Type::String if !slice && !integer => {
+                self.error(
+                    format!("Index musi byt cele cislo nebo rozsah, ne {}.", index),
+                    token,
+                );
+                Type::Any
+            }
+            Type::List if slice => Type::List,
+            Type::Tuple if slice => Type::Tuple,
+            Type::String => Type::String,
+            Type::List | Type::Tuple | Type::Any => Type::Any,
+            other => {
+                self.error(format!("Indexovat lze jen seznamy, ntice a retezce. Nalezeno {}.", other), token);
+                Type::Any
+            }
+        }
+    }
+
+    pub(crate) fn set_index(&mut self, object: &Type, index: &Type, token: &Token) {
+        if self.special(object, "__setitem__").is_some() {
+            return;
+        }
+        match object.required() {
+            Type::List if !matches!(index.required(), Type::Int | Type::Num | Type::Any) => {
+                self.error(format!("Index musi byt cele cislo, ne {}.", index), token)
+            }
+            Type::List | Type::Any => {}
+            Type::String => self.error(String::from("Retezce nelze menit."), token),
+            Type::Tuple => self.error(String::from("Ntice nelze menit."), token),
+            other => self.error(format!("Indexovat lze jen seznamy, ntice a retezce. Nalezeno {}.", other), token),
+        }
+    }
+
+    /// Typ prvku pri prochazeni `for (x in ...)`.
+    pub(crate) fn element(&mut self, iterable: &Type, token: &Token) -> Type {
+        match iterable.required() {
+            Type::String => Type::String,
+            Type::Range => Type::Int,
+            Type::List | Type::Tuple | Type::Set | Type::Generator | Type::Instance(_) | Type::Any => Type::Any,
+            other => {
+                self.error(format!("Hodnota typu {} neni iterovatelna.", other), token);
+                Type::Any
+            }
+        }
+    }
+
+    pub(crate) fn signature(
+        &mut self,
+        name: &str,
+        parameters: &[Parameter],
+        return_type: Option<&Annotation>,
+        generator: bool,
+    ) -> Rc<Signature> {
+        let types = parameters
+            .iter()
+            .map(|parameter| self.annotation(parameter.annotation.as_ref()))
+            .collect();
+        let returns = self.annotation(return_type);
+        Rc::new(Signature {
+            name: name.to_string(),
+            parameters: parameters.to_vec(),
+            types,
+            returns: if generator { Type::Generator } else { returns },
+        })
+    }
+
+    /// Zkontroluje telo funkce s parametry podle signatury. V generatoru
+    /// se `return` nekontroluje, volani vzdy vrati generator.
+    pub(crate) fn function_body(
+        &mut self,
+        name: &Token,
+        signature: &Signature,
+        body: &[Rc<dyn Statement>],
+        generator: bool,
+    ) {
+        self.begin_scope();
+        for (parameter, expected) in signature.parameters.iter().zip(&signature.types) {
+            if let Some(default) = &parameter.default {
+                let actual = default.check(self);
+                self.expect(expected, &actual, &parameter.name, || {
+                    format!(
+                        "Parametr '{}' ma typ {}, ale vychozi hodnota je {}.",
+                        parameter.name.lexeme, expected, actual
+                    )
+                });
+            }
+            let ty = match expected {
+                Type::Any if parameter.rest => Type::List,
+                other => other.clone(),
+            };
+            self.declare(&parameter.name.lexeme, ty);
+        }
+        let returns = if generator { Type::Any } else { signature.returns.clone() };
+        self.returns.push((name.clone(), returns));
+        for statement in body {
+            statement.check(self);
+        }
+        self.returns.pop();
+        self.end_scope();
+    }
+
+    pub(crate) fn return_value(&mut self, keyword: &Token, actual: &Type) {
+        if let Some((name, expected)) = self.returns.last().cloned() {
+            self.expect(&expected, actual, keyword, || {
+                format!("Funkce '{}' ma vracet {}, ale vraci {}.", name.lexeme, expected, actual)
+            });
+        }
+    }
+
+    pub(crate) fn this_type(&self) -> Type {
+        match self.this_class.last() {
+            Some(class) => Type::Instance(class.clone()),
+            None => Type::Any,
+        }
+    }
+
+    /// Zaregistruje tridu s poli a signaturami metod a pak zkontroluje
+    /// pocatecni hodnoty poli a tela metod. Trida je znama uz pri kontrole
+    /// anotaci vlastnich poli.
+    pub(crate) fn class(
+        &mut self,
+        name: &Token,
+        super_class: Option<Token>,
+        traits: &[Token],
+        fields: &[Field],
+        methods: &[(MethodKind, Rc<dyn Statement>)],
+    ) {
+        self.classes.insert(name.lexeme.clone(), ClassInfo::default());
+        let mut info = ClassInfo {
+            super_class: super_class.map(|token| token.lexeme),
+            traits: traits.iter().map(|token| token.lexeme.clone()).collect(),
+            ..ClassInfo::default()
+        };
+        let mut field_types = Vec::new();
+        for field in fields {
+            let ty = self.annotation(field.annotation.as_ref());
+            if field.annotation.is_some() {
+                info.fields.insert(field.name.lexeme.clone(), ty.clone());
+            }
+            field_types.push(ty);
+        }
+        let functions: Vec<(MethodKind, Function)> = methods
+            .iter()
+            .filter_map(|(kind, method)| match method.kind() {
+                StatementKind::Function(function) => Some((*kind, function)),
+                _ => None,
+            })
+            .collect();
+        let mut signatures = Vec::new();
+        for (kind, function) in &functions {
+            let signature = self.signature(
+                &function.name.lexeme,
+                &function.parameters,
+                function.return_type.as_ref(),
+                function.generator,
+            );
+            info.members_mut(*kind)
+                .insert(function.name.lexeme.clone(), Rc::clone(&signature));
+            signatures.push(signature);
+        }
+        self.classes.insert(name.lexeme.clone(), info);
+        self.declare(&name.lexeme, Type::Class(name.lexeme.clone()));
+
+        self.this_class.push(name.lexeme.clone());
+        for (field, expected) in fields.iter().zip(&field_types) {
+            if let Some(init) = &field.init {
+                let actual = init.check(self);
+                self.expect(expected, &actual, &field.name, || {
+                    format!(
+                        "Pole '{}' ma typ {}, ale hodnota je {}.",
+                        field.name.lexeme, expected, actual
+                    )
+                });
+            }
+        }
+        for ((_, function), signature) in functions.iter().zip(signatures) {
+            self.function_body(&function.name, &signature, &function.body, function.generator);
+        }
+        self.this_class.pop();
+    }
+
+    /// Zaregistruje trait s povinnymi i vychozimi metodami, aby sel pouzit
+    /// v anotacich, a zkontroluje tela vychozich metod.
+    pub(crate) fn trait_declaration(
+        &mut self,
+        name: &Token,
+        methods: &[Rc<dyn Statement>],
+        required: &[Function],
+    ) {
+        self.classes.insert(name.lexeme.clone(), ClassInfo::default());
+        let mut info = ClassInfo::default();
+        for function in required {
+            let signature = self.signature(
+                &function.name.lexeme,
+                &function.parameters,
+                function.return_type.as_ref(),
+                false,
+            );
+            info.methods.insert(function.name.lexeme.clone(), signature);
+        }
+        let functions: Vec<Function> = methods
+            .iter()
+            .filter_map(|method| match method.kind() {
+                StatementKind::Function(function) => Some(function),
+                _ => None,
+            })
+            .collect();
+        let mut signatures = Vec::new();
+        for function in &functions {
+            let signature = self.signature(
+                &function.name.lexeme,
+                &function.parameters,
+                function.return_type.as_ref(),
+                function.generator,
+            );
+            info.methods.insert(function.name.lexeme.clone(), Rc::clone(&signature));
+            signatures.push(signature);
+        }
+        self.classes.insert(name.lexeme.clone(), info);
+        self.declare(&name.lexeme, Type::Any);
+
+        self.this_class.push(name.lexeme.clone());
+        for (function, signature) in functions.iter().zip(signatures) {
+            self.function_body(&function.name, &signature, &function.body, function.generator);
+        }
+        self.this_class.pop();
+    }
+
+    /// Zaznam je pro kontrolu nemenna trida: pole maji typy z anotaci
+    /// parametru, konstruktor je `init` a `with` ma vsechny parametry volitelne.
+    pub(crate) fn record(&mut self, name: &Token, parameters: &[Parameter]) {
+        self.classes.insert(name.lexeme.clone(), ClassInfo::default());
+        let mut info = ClassInfo {
+            record: true,
+            ..ClassInfo::default()
+        };
+        let init = self.signature(&name.lexeme, parameters, None, false);
+        for (parameter, ty) in parameters.iter().zip(&init.types) {
+            info.fields.insert(parameter.name.lexeme.clone(), ty.clone());
+        }
+        let with = Signature {
+            name: String::from("with"),
+            parameters: parameters
+                .iter()
+                .map(|parameter| Parameter {
+                    default: Some(Rc::new(NoOp {})),
+                    ..parameter.clone()
+                })
+                .collect(),
+            types: init.types.clone(),
+            returns: Type::Instance(name.lexeme.clone()),
+        };
+        info.methods.insert(String::from("init"), Rc::clone(&init));
+        info.methods.insert(String::from("with"), Rc::new(with));
+        self.classes.insert(name.lexeme.clone(), info);
+        self.declare(&name.lexeme, Type::Class(name.lexeme.clone()));
+        self.function_body(name, &init, &[], false);
+    }
+
+    /// Vycet jde pouzit v anotacich jako typ svych variant. O variantach
+    /// samotnych kontrola nic nevi.
+    pub(crate) fn enum_declaration(&mut self, name: &Token) {
+        self.classes.insert(name.lexeme.clone(), ClassInfo::default());
+        self.declare(&name.lexeme, Type::Any);
+    }
+
+    /// Vetve `match`: zachycene promenne nemaji znamy typ.
+    pub(crate) fn match_arms<T>(
+        &mut self,
+        arms: &[MatchArm<T>],
+        body: impl Fn(&T, &mut Checker) -> Type,
+    ) -> Option<Type> {
+        let mut result: Option<Type> = None;
+        for arm in arms {
+            self.begin_scope();
+            for name in arm.pattern.bindings() {
+                self.declare(&name.lexeme, Type::Any);
+            }
+            if let Some(guard) = &arm.guard {
+                guard.check(self);
+            }
+            let ty = body(&arm.body, self);
+            self.end_scope();
+            result = Some(match result {
+                None => ty,
+                Some(previous) => self.unify(&previous, &ty),
+            });
+        }
+        result
+    }
+}
+
+/// Typ vysledku aritmeticke operace se dvema cisly.
+fn arithmetic(a: &Type, b: &Type, unknown: bool) -> Type {
+    match (a, b) {
+        _ if unknown => Type::Any,
+        (Type::Int, Type::Int) => Type::Int,
+        (Type::Float, _) | (_, Type::Float) => Type::Float,
+        _ => Type::Num,
+    }
+}
@@ -0,0 +1,128 @@
+use crate::natives::native_callable;
+use crate::token::Token;
+use crate::value::Value;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+/// Vycet z `enum Jmeno { A, B(x, y) }`. Varianty si pamatuji jmena svych
+/// hodnot, varianta bez hodnot je rovnou hodnotou, ostatni jsou konstruktory.
+#[derive(Debug)]
+pub struct Enum {
+    pub(crate) name: String,
+    pub(crate) variants: Vec<(String, Vec<String>)>,
+}
+
+/// Hodnota vyctu: ktera varianta to je a s jakymi daty.
+#[derive(Debug)]
+pub struct VariantValue {
+    pub(crate) owner: Rc<Enum>,
+    pub(crate) index: usize,
+    pub(crate) values: Vec<Value>,
+}
+
+impl Enum {
+    pub(crate) fn position(&self, name: &str) -> Option<usize> {
+        self.variants.iter().position(|(variant, _)| variant == name)
+    }
+
+    /// `Barva.Cervena` vrati variantu, `Tvar.Kruh` konstruktor varianty.
+    pub fn get_property(self: &Rc<Self>, name: &Token) -> Result<Value, (String, Token)> {
+        let index = match self.position(&name.lexeme) {
+            Some(index) => index,
+            None => {
+                return Err((
+                    format!("Vycet {} nema variantu '{}'.", self.name, name.lexeme),
+                    name.clone(),
+                ))
+            }
+        };
+        let fields = &self.variants[index].1;
+        if fields.is_empty() {
+            return Ok(self.variant(index, Vec::new()));
+        }
+        let owner = Rc::clone(self);
+        let parameters: Vec<&str> = fields.iter().map(|field| field.as_str()).collect();
+        let mut constructor = native_callable(&name.lexeme, &parameters, move |arguments| {
+            Ok(owner.variant(index, arguments.to_vec()))
+        });
+        constructor.string = format!("<variant {}.{}>", self.name, name.lexeme);
+        Ok(Value::Function(Rc::new(constructor)))
+    }
+
+    fn variant(self: &Rc<Self>, index: usize, values: Vec<Value>) -> Value {
+        Value::Variant(Rc::new(VariantValue {
+            owner: Rc::clone(self),
+            index,
+            values,
+        }))
+    }
+
+    /// Varianty pro `for (x in Vycet)`. Jde to jen u vyctu bez dat.
+    pub fn variants(self: &Rc<Self>, token: &Token) -> Result<Value, (String, Token)> {
+        if self.variants.iter().any(|(_, fields)| !fields.is_empty()) {
+            return Err((
+                format!("Vycet {} ma varianty s daty, nelze ho prochazet.", self.name),
+                token.clone(),
+            ));
+        }
+        let variants = (0..self.variants.len())
+            .map(|index| self.variant(index, Vec::new()))
+            .collect();
+        Ok(Value::List(Rc::new(RefCell::new(variants))))
+    }
+}
+
+impl VariantValue {
+    pub fn name(&self) -> &str {
+        &self.owner.variants[self.index].0
+    }
+
+    fn fields(&self) -> &[String] {
+        &self.owner.variants[self.index].1
+    }
+
+    pub fn field(&self, name: &str) -> Option<Value> {
+        let index = self.fields().iter().position(|field| field == name)?;
+        Some(self.values[index].clone())
+    }
+
+    /// Data varianty podle jmena z deklarace, `kruh.polomer`.
+    pub fn get_property(&self, name: &Token) -> Result<Value, (String, Token)> {
+        match self.field(&name.lexeme) {
+            Some(value) => Ok(value),
+            None => Err((
+                format!("Varianta {} nema hodnotu '{}'.", self.name(), name.lexeme),
+                name.clone(),
+            )),
+        }
+    }
+
+    /// Jestli je to varianta `variant` vyctu `owner` (podle jmen).
+    pub fn is(&self, owner: &str, variant: &str) -> bool {
+        self.owner.name == owner && self.name() == variant
+    }
+}
+
+impl PartialEq for VariantValue {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.owner, &other.owner) && self.index == other.index && self.values == other.values
+    }
+}
+
+impl fmt::Display for VariantValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.owner.name, self.name())?;
+        if self.fields().is_empty() {
+            return Ok(());
+        }
+        write!(f, "(")?;
+        for (i, value) in self.values.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        write!(f, ")")
+    }
+}
@@ -1,18 +1,27 @@
+use crate::bigint::BigInt;
+use crate::checker::Checker;
 use crate::moonenv::Environment;
-use crate::value::Value;
+use crate::patterns::{is_subclass, select_arm, uses_trait, MatchArm, Pattern};
+use crate::statements::{make_function, Statement};
+use crate::sets::SetValue;
+use crate::value::{InstanceValue, Parameter, RangeValue, Value};
 use crate::token::Token;
+use crate::types::{Annotation, Type};
 use crate::tokentype::TokenType;
+use std::cell::RefCell;
 use std::rc::Rc;
 
 pub trait Expr {
     fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)>;
     fn kind(&self) -> Kind;
+    /// Typ vyrazu pro `moon check`; nesedici typy hlasi do `checker`.
+    fn check(&self, checker: &mut Checker) -> Type;
 }
 
 pub enum Kind {
     Binary,
-    Literal,
-    Unary,
+    Literal(Value),
+    Unary(Token, Rc<dyn Expr>),
     Grouping,
     Variable(Token),
     NoOp,
@@ -23,6 +32,22 @@ pub enum Kind {
     This,
     Super,
     Set,
+    CompoundAssign,
+    CompoundSet,
+    Increment,
+    Conditional,
+    Coalesce,
+    Is,
+    OptionalChain,
+    Lambda,
+    List(Vec<Rc<dyn Expr>>),
+    Tuple(Vec<Rc<dyn Expr>>),
+    SetLiteral,
+    Range,
+    Index(Token, Rc<dyn Expr>, Rc<dyn Expr>),
+    SetIndex,
+    Match,
+    DestructuringAssign,
 }
 
 pub struct Binary {
@@ -35,56 +60,303 @@ impl Expr for Binary {
     fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
         let left = self.left.evaluate(Rc::clone(&env))?;
         let right = self.right.evaluate(Rc::clone(&env))?;
-        let token = self.op.clone();
-        match self.op.token_type {
-            TokenType::BangEqual => Ok(is_equal(left, right, true)),
-            TokenType::EqualEqual => Ok(is_equal(left, right, false)),
-            TokenType::Greater => match (left, right) {
-                (Value::Number(a), Value::Number(b)) => Ok(Value::Bool(a > b)),
-                _ => Err((String::from("Lze porovnat jen 2 cisla."), token)),
+        binary_operation(self.op.token_type.clone(), self.op.clone(), left, right)
+    }
+
+    fn check(&self, checker: &mut Checker) -> Type {
+        let left = self.left.check(checker);
+        let right = self.right.check(checker);
+        let result = checker.binary(&self.op.token_type, &self.op, &left, &right);
+        // Cela cisla umocnena na zaporny exponent daji desetinne cislo.
+        if self.op.token_type == TokenType::StarStar && result == Type::Int && is_negative_literal(&self.right) {
+            return Type::Float;
+        }
+        result
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Binary 
+    }
+}
+
+fn is_negative_literal(expr: &Rc<dyn Expr>) -> bool {
+    match expr.kind() {
+        Kind::Unary(operator, operand) => {
+            operator.token_type == TokenType::Minus
+                && matches!(operand.kind(), Kind::Literal(Value::Int(_) | Value::BigInt(_)))
+        }
+        _ => false,
+    }
+}
+
+pub(crate) fn binary_operation(
+    operator: TokenType,
+    token: Token,
+    left: Value,
+    right: Value,
+) -> Result<Value, (String, Token)> {
+        if let Some(result) = special_operation(&operator, &left, &right) {
+            return result;
+        }
+        if let Some(result) = set_operation(&operator, &token, &left, &right) {
+            return result;
+        }
+        match operator {
+            TokenType::BangEqual => Ok(Value::Bool(!left.equals(&right)?)),
+            TokenType::EqualEqual => Ok(Value::Bool(left.equals(&right)?)),
+            TokenType::Greater => match numbers(&left, &right) {
+                Some(Numbers::Int(a, b)) => Ok(Value::Bool(a > b)),
+                Some(Numbers::Float(a, b)) => Ok(Value::Bool(a > b)),
+                Some(Numbers::Big(a, b)) => Ok(Value::Bool(a > b)),
+                None => Err((String::from("Lze porovnat jen 2 cisla."), token)),
             },
-            TokenType::GreaterEqual => match (left, right) {
-                (Value::Number(a), Value::Number(b)) => Ok(Value::Bool(a >= b)),
-                _ => Err((String::from("Lze porovnat jen 2 cisla."), token)),
+            TokenType::GreaterEqual => match numbers(&left, &right) {
+                Some(Numbers::Int(a, b)) => Ok(Value::Bool(a >= b)),
+                Some(Numbers::Float(a, b)) => Ok(Value::Bool(a >= b)),
+                Some(Numbers::Big(a, b)) => Ok(Value::Bool(a >= b)),
+                None => Err((String::from("Lze porovnat jen 2 cisla."), token)),
             },
-            TokenType::Less => match (left, right) {
-                (Value::Number(a), Value::Number(b)) => Ok(Value::Bool(a < b)),
-                _ => Err((String::from("Lze porovnat jen 2 cisla."), token)),
+            TokenType::Less => match numbers(&left, &right) {
+                Some(Numbers::Int(a, b)) => Ok(Value::Bool(a < b)),
+                Some(Numbers::Float(a, b)) => Ok(Value::Bool(a < b)),
+                Some(Numbers::Big(a, b)) => Ok(Value::Bool(a < b)),
+                None => Err((String::from("Lze porovnat jen 2 cisla."), token)),
             },
-            TokenType::LessEqual => match (left, right) {
-                (Value::Number(a), Value::Number(b)) => Ok(Value::Bool(a <= b)),
-                _ => Err((String::from("Lze porovnat jen 2 cisla."), token)),
+            TokenType::LessEqual => match numbers(&left, &right) {
+                Some(Numbers::Int(a, b)) => Ok(Value::Bool(a <= b)),
+                Some(Numbers::Float(a, b)) => Ok(Value::Bool(a <= b)),
+                Some(Numbers::Big(a, b)) => Ok(Value::Bool(a <= b)),
+                None => Err((String::from("Lze porovnat jen 2 cisla."), token)),
             },
-            TokenType::Minus => match (left, right) {
-                (Value::Number(a), Value::Number(b)) => Ok(Value::Number(a.clone() - b.clone())),
-                _ => Err((String::from("Lze odecist jen 2 cisla."), token)),
+            TokenType::Minus => match numbers(&left, &right) {
+                Some(Numbers::Int(a, b)) => Ok(integer_op(a, b, i64::checked_sub, BigInt::sub)),
+                Some(Numbers::Float(a, b)) => Ok(Value::Number(a - b)),
+                Some(Numbers::Big(a, b)) => Ok(Value::integer(a.sub(&b))),
+                None => Err((String::from("Lze odecist jen 2 cisla."), token)),
             },
             TokenType::Plus => match (left, right) {
-                (Value::Number(a), Value::Number(b)) => Ok(Value::Number(a.clone() + b.clone())),
                 (Value::String(a), Value::String(b)) => Ok(Value::String(format!("{}{}", a, b))),
-                _ => Err((String::from("Lze spojit/secist jen dva retezce/cisla."), token)),
+                (left, right) => match numbers(&left, &right) {
+                    Some(Numbers::Int(a, b)) => Ok(integer_op(a, b, i64::checked_add, BigInt::add)),
+                    Some(Numbers::Float(a, b)) => Ok(Value::Number(a + b)),
+                    Some(Numbers::Big(a, b)) => Ok(Value::integer(a.add(&b))),
+                    None => Err((String::from("Lze spojit/secist jen dva retezce/cisla."), token)),
+                },
+            },
+            TokenType::Slash => match numbers(&left, &right) {
+                Some(Numbers::Int(_, 0)) => Err((String::from("Nelze delit nulou."), token)),
+                Some(Numbers::Int(a, b)) => Ok(Value::Number(a as f64 / b as f64)),
+                Some(Numbers::Float(a, b)) => {
+                    if b == 0.0 {
+                        Err((String::from("Nelze delit nulou."), token))
+                    } else {
+                        Ok(Value::Number(a / b))
+                    }
+                }
+                Some(Numbers::Big(a, b)) => {
+                    if b.is_zero() {
+                        Err((String::from("Nelze delit nulou."), token))
+                    } else {
+                        Ok(Value::Number(a.to_f64() / b.to_f64()))
+                    }
+                }
+                None => Err((String::from("Lze delit jen 2 cisla."), token)),
             },
-            TokenType::Slash => match (left, right) {
-                (Value::Number(a), Value::Number(b)) => {
-                    if b.clone() == 0.0 {
+            TokenType::TildeSlash => match numbers(&left, &right) {
+                Some(Numbers::Int(_, 0)) => Err((String::from("Nelze delit nulou."), token)),
+                Some(Numbers::Int(a, b)) => Ok(integer_op(a, b, floor_div, |a, b| {
+                    a.div_mod_floor(b).unwrap().0
+                })),
+                Some(Numbers::Float(a, b)) => {
+                    if b == 0.0 {
                         Err((String::from("Nelze delit nulou."), token))
                     } else {
-                        Ok(Value::Number(a.clone() / b.clone()))
+                        Ok(Value::Number((a / b).floor()))
+                    }
+                }
+                Some(Numbers::Big(a, b)) => match a.div_mod_floor(&b) {
+                    Some((quotient, _)) => Ok(Value::integer(quotient)),
+                    None => Err((String::from("Nelze delit nulou."), token)),
+                },
+                None => Err((String::from("Lze delit jen 2 cisla."), token)),
+            },
+            TokenType::Star => match numbers(&left, &right) {
+                Some(Numbers::Int(a, b)) => Ok(integer_op(a, b, i64::checked_mul, BigInt::mul)),
+                Some(Numbers::Float(a, b)) => Ok(Value::Number(a * b)),
+                Some(Numbers::Big(a, b)) => Ok(Value::integer(a.mul(&b))),
+                None => Err((String::from("Lze nasobit jen 2 cisla."), token)),
+            },
+            TokenType::Percent => match numbers(&left, &right) {
+                Some(Numbers::Int(_, 0)) => Err((String::from("Nelze delit nulou."), token)),
+                Some(Numbers::Int(a, b)) => Ok(integer_op(a, b, floor_mod, |a, b| {
+                    a.div_mod_floor(b).unwrap().1
+                })),
+                Some(Numbers::Float(a, b)) => {
+                    if b == 0.0 {
+                        return Err((String::from("Nelze delit nulou."), token));
+                    }
+                    let remainder = a % b;
+                    if remainder != 0.0 && (remainder < 0.0) != (b < 0.0) {
+                        Ok(Value::Number(remainder + b))
+                    } else {
+                        Ok(Value::Number(remainder))
                     }
                 }
-                _ => Err((String::from("Lze delit jen 2 cisla."), token)),
+                Some(Numbers::Big(a, b)) => match a.div_mod_floor(&b) {
+                    Some((_, remainder)) => Ok(Value::integer(remainder)),
+                    None => Err((String::from("Nelze delit nulou."), token)),
+                },
+                None => Err((String::from("Zbytek po deleni lze spocitat jen u 2 cisel."), token)),
             },
-            TokenType::Star => match (left, right) {
-                (Value::Number(a), Value::Number(b)) => Ok(Value::Number(a.clone() * b.clone())),
-                _ => Err((String::from("Lze nasobit jen 2 cisla."), token)),
+            TokenType::StarStar => match numbers(&left, &right) {
+                Some(Numbers::Int(a, b)) if b < 0 => Ok(Value::Number((a as f64).powf(b as f64))),
+                Some(Numbers::Int(a, b)) => match u32::try_from(b).ok().and_then(|b| a.checked_pow(b)) {
+                    Some(result) => Ok(Value::Int(result)),
+                    None => power(BigInt::from(a), b as u64, token),
+                },
+                Some(Numbers::Big(a, b)) => match b.to_i64() {
+                    Some(exponent) if exponent < 0 => Ok(Value::Number(a.to_f64().powf(exponent as f64))),
+                    Some(exponent) => power(a, exponent as u64, token),
+                    None => Err((String::from("Exponent je prilis velky."), token)),
+                },
+                Some(Numbers::Float(a, b)) => Ok(Value::Number(a.powf(b))),
+                None => Err((String::from("Umocnit lze jen 2 cisla."), token)),
             },
+            TokenType::Ampersand => bitwise(left, right, token, |a, b| a & b, BigInt::and),
+            TokenType::Pipe => bitwise(left, right, token, |a, b| a | b, BigInt::or),
+            TokenType::Caret => bitwise(left, right, token, |a, b| a ^ b, BigInt::xor),
+            TokenType::LessLess => shift(left, right, token, false),
+            TokenType::GreaterGreater => shift(left, right, token, true),
+            TokenType::In => Ok(Value::Bool(contains(&left, &right, token)?)),
+            TokenType::NotIn => Ok(Value::Bool(!contains(&left, &right, token)?)),
             _ => Err((String::from("Neznama operace."), token)),
         }
+}
+
+/// `|`, `&` a `-` nad dvema mnozinami: sjednoceni, prunik a rozdil.
+fn set_operation(
+    operator: &TokenType,
+    token: &Token,
+    left: &Value,
+    right: &Value,
+) -> Option<Result<Value, (String, Token)>> {
+    let (a, b) = match (left, right) {
+        (Value::Set(a), Value::Set(b)) => (a.borrow(), b.borrow()),
+        _ => return None,
+    };
+    let result = match operator {
+        TokenType::Pipe => a.union(&b, token),
+        TokenType::Ampersand => a.intersection(&b, token),
+        TokenType::Minus => a.difference(&b, token),
+        _ => return None,
+    };
+    Some(result.map(SetValue::into_value))
+}
+
+fn any_equal(items: &[Value], element: &Value) -> Result<bool, (String, Token)> {
+    for item in items {
+        if item.equals(element)? {
+            return Ok(true);
+        }
     }
+    Ok(false)
+}
 
-    fn kind(&self) -> Kind {
-        Kind::Binary 
+/// `prvek in kolekce`. V retezci se hleda podretezec, instance muzou
+/// definovat `__contains__` (to se zkusi uz ve `special_operation`).
+fn contains(element: &Value, container: &Value, token: Token) -> Result<bool, (String, Token)> {
+    let found = match (element, container) {
+        (_, Value::List(list)) => any_equal(&list.borrow(), element)?,
+        (_, Value::Tuple(items)) => any_equal(items, element)?,
+        (_, Value::Set(set)) => set.borrow().contains(element)?,
+        (_, Value::Range(range)) => range.contains(element),
+        (Value::String(a), Value::String(b)) => b.contains(a.as_str()),
+        (_, Value::String(_)) => {
+            return Err((String::from("V retezci lze hledat jen retezec."), token));
+        }
+        _ => {
+            return Err((
+                String::from("Operator 'in' lze pouzit jen na seznamy, ntice, mnoziny, retezce, rozsahy a instance s metodou __contains__."),
+                token,
+            ))
+        }
+    };
+    Ok(found)
+}
+
+/// Specialni metoda pro binarni operator: jmeno, jestli se prohodi operandy
+/// a jestli se vysledek neguje. Pro porovnani tak staci `__eq__` a `__lt__`.
+pub(crate) fn special_method(operator: &TokenType) -> Option<(&'static str, bool, bool)> {
+    match operator {
+        TokenType::Plus => Some(("__add__", false, false)),
+        TokenType::Minus => Some(("__sub__", false, false)),
+        TokenType::Star => Some(("__mul__", false, false)),
+        TokenType::Slash => Some(("__div__", false, false)),
+        TokenType::Percent => Some(("__mod__", false, false)),
+        TokenType::EqualEqual => Some(("__eq__", false, false)),
+        TokenType::BangEqual => Some(("__eq__", false, true)),
+        TokenType::Less => Some(("__lt__", false, false)),
+        TokenType::Greater => Some(("__lt__", true, false)),
+        TokenType::LessEqual => Some(("__lt__", true, true)),
+        TokenType::GreaterEqual => Some(("__lt__", false, true)),
+        TokenType::In => Some(("__contains__", true, false)),
+        TokenType::NotIn => Some(("__contains__", true, true)),
+        _ => None,
+    }
+}
+
+/// Operator nad instanci, jejiz trida definuje odpovidajici specialni metodu.
+/// Vysledek porovnani se prevede na `true`/`false`.
+fn special_operation(
+    operator: &TokenType,
+    left: &Value,
+    right: &Value,
+) -> Option<Result<Value, (String, Token)>> {
+    let (name, swap, negate) = special_method(operator)?;
+    let (receiver, argument) = if swap { (right, left) } else { (left, right) };
+    let result = match (receiver, argument) {
+        (Value::Instance(instance), _) => instance.call_special(name, vec![argument.clone()])?,
+        (_, Value::Instance(instance)) if name == "__lt__" => {
+            return Some(reflected_less(instance, receiver)?.map(|less| Value::Bool(less != negate)))
+        }
+        _ => return None,
+    };
+    if is_comparison(operator) {
+        return Some(result.and_then(|value| is_truth(value, negate)));
     }
+    Some(result)
+}
+
+/// `x < v`, kdyz je instance jen napravo (`5 < v`, `v > 5`). Odvodi se
+/// z jejich `__lt__` a `__eq__`: `x < v` plati, kdyz neplati `v < x` ani `v == x`.
+fn reflected_less(instance: &Rc<InstanceValue>, other: &Value) -> Option<Result<bool, (String, Token)>> {
+    let truthy = |value: Value| !matches!(value, Value::Bool(false) | Value::None);
+    let less = match instance.call_special("__lt__", vec![other.clone()])? {
+        Ok(value) => truthy(value),
+        Err(err) => return Some(Err(err)),
+    };
+    if less {
+        return Some(Ok(false));
+    }
+    match instance.call_special("__eq__", vec![other.clone()]) {
+        Some(Ok(value)) => Some(Ok(!truthy(value))),
+        Some(Err(err)) => Some(Err(err)),
+        None => Some(Ok(true)),
+    }
+}
+
+pub(crate) fn is_comparison(operator: &TokenType) -> bool {
+    matches!(
+        operator,
+        TokenType::EqualEqual
+            | TokenType::BangEqual
+            | TokenType::Less
+            | TokenType::Greater
+            | TokenType::LessEqual
+            | TokenType::GreaterEqual
+            | TokenType::In
+            | TokenType::NotIn
+    )
 }
 
 pub struct Grouping {
@@ -96,6 +368,10 @@ impl Expr for Grouping {
         self.expression.evaluate(env)
     }
 
+    fn check(&self, checker: &mut Checker) -> Type {
+        self.expression.check(checker)
+    }
+
     fn kind(&self) -> Kind {
         Kind::Grouping
     }
@@ -110,8 +386,12 @@ impl Expr for Literal {
         Ok(self.value.clone())
     }
 
+    fn check(&self, _checker: &mut Checker) -> Type {
+        Checker::value_type(&self.value)
+    }
+
     fn kind(&self) -> Kind {
-        Kind::Literal
+        Kind::Literal(self.value.clone())
     }
 }
 
@@ -125,16 +405,38 @@ impl Expr for Unary {
         let right = self.right.evaluate(env)?;
         match self.operator.token_type {
             TokenType::Minus => match right {
-                Value::Number(a) => Ok(Value::Number(-a.clone())),
+                Value::Number(a) => Ok(Value::Number(-a)),
+                Value::Int(a) => Ok(a
+                    .checked_neg()
+                    .map(Value::Int)
+                    .unwrap_or_else(|| Value::integer(BigInt::from(a).neg()))),
+                Value::BigInt(a) => Ok(Value::integer(a.neg())),
+                Value::Instance(instance) => match instance.call_special("__neg__", Vec::new()) {
+                    Some(result) => result,
+                    None => Err((String::from("Jsou mozna jen zaporna cisla."), self.operator.clone())),
+                },
                 _ => Err((String::from("Jsou mozna jen zaporna cisla."), self.operator.clone())),
             },
             TokenType::Bang => is_truth(right, true),
+            TokenType::Tilde => match right {
+                Value::Int(a) => Ok(Value::Int(!a)),
+                Value::BigInt(a) => Ok(Value::integer(a.not())),
+                _ => Err((
+                    String::from("Bitovou negaci lze pouzit jen na cela cisla."),
+                    self.operator.clone(),
+                )),
+            },
             _ => Err((String::from("Neznama operace"), self.operator.clone())),
         }
     }
 
+    fn check(&self, checker: &mut Checker) -> Type {
+        let right = self.right.check(checker);
+        checker.unary(&self.operator, &right)
+    }
+
     fn kind(&self) -> Kind {
-        Kind::Unary
+        Kind::Unary(self.operator.clone(), Rc::clone(&self.right))
     }
 }
 
@@ -150,6 +452,10 @@ impl Expr for Variable {
         }
     }
 
+    fn check(&self, checker: &mut Checker) -> Type {
+        checker.lookup(&self.name.lexeme)
+    }
+
     fn kind(&self) -> Kind {
         Kind::Variable(self.name.clone())
     }
@@ -162,6 +468,10 @@ impl Expr for NoOp {
         Ok(Value::None)
     }
 
+    fn check(&self, _checker: &mut Checker) -> Type {
+        Type::Nil
+    }
+
     fn kind(&self) -> Kind {
         Kind::NoOp
     }
@@ -181,6 +491,15 @@ impl Expr for Assign {
         }
     }
 
+    fn check(&self, checker: &mut Checker) -> Type {
+        let value = self.value.check(checker);
+        let expected = checker.lookup(&self.name.lexeme);
+        checker.expect(&expected, &value, &self.name, || {
+            format!("Promenna '{}' ma typ {}, ale hodnota je {}.", self.name.lexeme, expected, value)
+        });
+        value
+    }
+
     fn kind(&self) -> Kind {
         Kind::Assign
     }
@@ -207,6 +526,12 @@ impl Expr for Logical {
         }
     }
 
+    fn check(&self, checker: &mut Checker) -> Type {
+        let left = self.left.check(checker);
+        let right = self.right.check(checker);
+        checker.unify(&left, &right)
+    }
+
     fn kind(&self) -> Kind {
         Kind::Logical
     }
@@ -216,6 +541,19 @@ pub struct Call {
     pub(crate) calling: Rc<dyn Expr>,
     pub(crate) parent: Token,
     pub(crate) arguments: Vec<Rc<dyn Expr>>,
+    pub(crate) named: Vec<(Token, Rc<dyn Expr>)>,
+}
+
+impl Call {
+    /// Nativni funkce hlasi chyby s vlastnim tokenem bez radku,
+    /// takova chyba se ukaze na miste volani.
+    fn at_call_site(&self, (msg, token): (String, Token)) -> (String, Token) {
+        if token.line == 0 {
+            (msg, self.parent.clone())
+        } else {
+            (msg, token)
+        }
+    }
 }
 
 impl Expr for Call {
@@ -225,25 +563,54 @@ impl Expr for Call {
         for argument in &self.arguments {
             arguments.push(argument.evaluate(Rc::clone(&env))?);
         }
+        let mut named: Vec<(Token, Value)> = Vec::new();
+        for (name, argument) in &self.named {
+            named.push((name.clone(), argument.evaluate(Rc::clone(&env))?));
+        }
+        let names: Vec<Token> = self.named.iter().map(|(name, _)| name.clone()).collect();
         match function {
             Value::Function(callable) => {
-                if callable.arity != arguments.len() {
-                    Err((format!("Ocekavano {} argumentu ale bylo zadano {}.", callable.arity, arguments.len()), self.parent.clone()))
-                } else {
-                    match callable.call(arguments) {
-                        Ok(a) => Ok(a),
-                        Err((msg, token)) => Err((msg, token.clone())),
-                    }
+                match callable.check_arguments(arguments.len(), &names) {
+                    Err(msg) => Err((msg, self.parent.clone())),
+                    Ok(_) => callable.call(arguments, named).map_err(|error| self.at_call_site(error)),
+                }
+            }
+            Value::Class(class) => match class.check_arguments(arguments.len(), &names) {
+                Err(msg) => Err((msg, self.parent.clone())),
+                Ok(_) => class.call(arguments, named),
+            },
+            Value::RecordType(record) => {
+                let constructor = record.constructor();
+                match constructor.check_arguments(arguments.len(), &names) {
+                    Err(msg) => Err((msg, self.parent.clone())),
+                    Ok(_) => constructor.call(arguments, named),
                 }
             }
-            Value::Class(class) => match class.call(arguments) {
-                Ok(a) => Ok(a),
-                Err((msg, token)) => Err((msg, token.clone())),
+            Value::Instance(instance) => match instance.class.find_method(String::from("__call__")) {
+                Some(callable) => {
+                    callable.bind(Value::Instance(Rc::clone(&instance)));
+                    match callable.check_arguments(arguments.len(), &names) {
+                        Err(msg) => Err((msg, self.parent.clone())),
+                        Ok(_) => callable.call(arguments, named),
+                    }
+                }
+                None => Err((String::from("Lze volat jen funkce a tridy."), self.parent.clone())),
             },
             _ => Err((String::from("Lze volat jen funkce a tridy."), self.parent.clone())),
         }
     }
 
+    fn check(&self, checker: &mut Checker) -> Type {
+        let callee = self.calling.check(checker);
+        let arguments: Vec<Type> = self.arguments.iter().map(|argument| argument.check(checker)).collect();
+        let named: Vec<(Token, Type)> = self
+            .named
+            .iter()
+            .map(|(name, argument)| (name.clone(), argument.check(checker)))
+            .collect();
+        checker.call(&callee, &self.parent, &arguments, &named)
+    }
+
     fn kind(&self) -> Kind {
         Kind::Call 
     }
@@ -257,11 +624,12 @@ pub struct Get {
 impl Expr for Get {
     fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
         let object = self.object.evaluate(env)?;
-        match object {
-            Value::Instance(instance) => instance.get_value(&self.name),
+        get_property(&object, &self.name)
+    }
 
-            _ => Err((String::from("Jen instance maji vlastnosti."), self.name.clone())),
-        }
+    fn check(&self, checker: &mut Checker) -> Type {
+        let object = self.object.check(checker);
+        checker.get_property(&object, &self.name)
     }
 
     fn kind(&self) -> Kind {
@@ -269,6 +637,20 @@ impl Expr for Get {
     }
 }
 
+pub(crate) fn get_property(object: &Value, name: &Token) -> Result<Value, (String, Token)> {
+    match object {
+        Value::Instance(instance) => instance.get_value(name),
+        Value::Range(range) => range.get_property(name),
+        Value::Generator(generator) => generator.get_property(name),
+        Value::Class(class) => class.get_static(name),
+        Value::Enum(owner) => owner.get_property(name),
+        Value::Variant(variant) => variant.get_property(name),
+        Value::Record(value) => value.get_property(name),
+        Value::Set(set) => SetValue::get_property(set, name),
+        _ => Err((String::from("Jen instance maji vlastnosti."), name.clone())),
+    }
+}
+
 pub struct Set {
     pub(crate) object: Rc<dyn Expr>,
     pub(crate) name: Token,
@@ -281,18 +663,626 @@ impl Expr for Set {
         match object {
             Value::Instance(a) => {
                 let value = self.value.evaluate(Rc::clone(&env))?;
-                a.set_value(self.name.lexeme.clone(), value.clone());
+                a.set_value(&self.name, value.clone())?;
                 Ok(value)
             }
+            Value::Record(value) => Err((
+                format!("Zaznam {} nelze menit.", value.record.name.lexeme),
+                self.name.clone(),
+            )),
             _ => Err((String::from("Jen instance maji pole."), self.name.clone())),
         }
     }
 
+    fn check(&self, checker: &mut Checker) -> Type {
+        let object = self.object.check(checker);
+        let value = self.value.check(checker);
+        checker.set_property(&object, &self.name, &value);
+        value
+    }
+
     fn kind(&self) -> Kind {
         Kind::Set 
     }
 }
 
+pub struct CompoundAssign {
+    pub(crate) name: Token,
+    pub(crate) op: Token,
+    pub(crate) value: Rc<dyn Expr>,
+}
+
+impl Expr for CompoundAssign {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let current = match env.get(&self.name) {
+            Ok(a) => a,
+            Err(msg) => return Err((msg, self.name.clone())),
+        };
+        let value = self.value.evaluate(Rc::clone(&env))?;
+        let result = binary_operation(compound_operator(&self.op), self.op.clone(), current, value)?;
+        match env.assign(&self.name, result.clone()) {
+            Ok(_) => Ok(result),
+            Err((msg, _token)) => Err((msg, self.name.clone())),
+        }
+    }
+
+    fn check(&self, checker: &mut Checker) -> Type {
+        let current = checker.lookup(&self.name.lexeme);
+        let value = self.value.check(checker);
+        let result = checker.binary(&compound_operator(&self.op), &self.op, &current, &value);
+        checker.expect(&current, &result, &self.name, || {
+            format!("Promenna '{}' ma typ {}, ale hodnota je {}.", self.name.lexeme, current, result)
+        });
+        result
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::CompoundAssign
+    }
+}
+
+pub struct CompoundSet {
+    pub(crate) object: Rc<dyn Expr>,
+    pub(crate) name: Token,
+    pub(crate) op: Token,
+    pub(crate) value: Rc<dyn Expr>,
+}
+
+impl Expr for CompoundSet {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let object = self.object.evaluate(Rc::clone(&env))?;
+        match object {
+            Value::Instance(a) => {
+                let current = a.get_value(&self.name)?;
+                let value = self.value.evaluate(Rc::clone(&env))?;
+                let result = binary_operation(compound_operator(&self.op), self.op.clone(), current, value)?;
+                a.set_value(&self.name, result.clone())?;
+                Ok(result)
+            }
+            _ => Err((String::from("Jen instance maji pole."), self.name.clone())),
+        }
+    }
+
+    fn check(&self, checker: &mut Checker) -> Type {
+        let object = self.object.check(checker);
+        let current = checker.get_property(&object, &self.name);
+        let value = self.value.check(checker);
+        let result = checker.binary(&compound_operator(&self.op), &self.op, &current, &value);
+        checker.set_property(&object, &self.name, &result);
+        result
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::CompoundSet
+    }
+}
+
+pub struct Increment {
+    pub(crate) name: Token,
+    pub(crate) object: Option<Rc<dyn Expr>>,
+    pub(crate) index: Option<Rc<dyn Expr>>,
+    pub(crate) op: Token,
+    pub(crate) prefix: bool,
+}
+
+impl Expr for Increment {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let one = Value::Int(1);
+        let operator = compound_operator(&self.op);
+        let (current, result) = match &self.object {
+            None => {
+                let current = match env.get(&self.name) {
+                    Ok(a) => a,
+                    Err(msg) => return Err((msg, self.name.clone())),
+                };
+                let result = binary_operation(operator, self.op.clone(), current.clone(), one)?;
+                if let Err((msg, _token)) = env.assign(&self.name, result.clone()) {
+                    return Err((msg, self.name.clone()));
+                }
+                (current, result)
+            }
+            Some(object) => match object.evaluate(Rc::clone(&env))? {
+                target if self.index.is_some() => {
+                    let index = self.index.as_ref().unwrap().evaluate(Rc::clone(&env))?;
+                    let current = get_index(&target, &index, &self.name)?;
+                    let result = binary_operation(operator, self.op.clone(), current.clone(), one)?;
+                    set_index(&target, &index, result.clone(), &self.name)?;
+                    (current, result)
+                }
+                Value::Instance(a) => {
+                    let current = a.get_value(&self.name)?;
+                    let result = binary_operation(operator, self.op.clone(), current.clone(), one)?;
+                    a.set_value(&self.name, result.clone())?;
+                    (current, result)
+                }
+                _ => return Err((String::from("Jen instance maji pole."), self.name.clone())),
+            },
+        };
+        if self.prefix {
+            Ok(result)
+        } else {
+            Ok(current)
+        }
+    }
+
+    fn check(&self, checker: &mut Checker) -> Type {
+        let current = match (&self.object, &self.index) {
+            (None, _) => checker.lookup(&self.name.lexeme),
+            (Some(object), Some(index)) => {
+                let object = object.check(checker);
+                let index = index.check(checker);
+                checker.index(&object, &index, &self.name)
+            }
+            (Some(object), None) => {
+                let object = object.check(checker);
+                checker.get_property(&object, &self.name)
+            }
+        };
+        let result = checker.binary(&compound_operator(&self.op), &self.op, &current, &Type::Int);
+        if self.prefix {
+            result
+        } else {
+            current
+        }
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Increment
+    }
+}
+
+pub struct Conditional {
+    pub(crate) condition: Rc<dyn Expr>,
+    pub(crate) then_branch: Rc<dyn Expr>,
+    pub(crate) else_branch: Rc<dyn Expr>,
+}
+
+impl Expr for Conditional {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        match is_truth(self.condition.evaluate(Rc::clone(&env))?, false)? {
+            Value::Bool(true) => self.then_branch.evaluate(env),
+            _ => self.else_branch.evaluate(env),
+        }
+    }
+
+    fn check(&self, checker: &mut Checker) -> Type {
+        self.condition.check(checker);
+        let then_branch = self.then_branch.check(checker);
+        let else_branch = self.else_branch.check(checker);
+        checker.unify(&then_branch, &else_branch)
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Conditional
+    }
+}
+
+pub struct Coalesce {
+    pub(crate) left: Rc<dyn Expr>,
+    pub(crate) right: Rc<dyn Expr>,
+}
+
+impl Expr for Coalesce {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        match self.left.evaluate(Rc::clone(&env))? {
+            Value::None => self.right.evaluate(env),
+            value => Ok(value),
+        }
+    }
+
+    fn check(&self, checker: &mut Checker) -> Type {
+        let left = self.left.check(checker);
+        let right = self.right.check(checker);
+        match left {
+            Type::Nil => right,
+            left => checker.unify(left.required(), &right),
+        }
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Coalesce
+    }
+}
+
+/// `x is Trida` nebo `x is Trait`: jestli je hodnota instanci tridy,
+/// jejiho potomka, nebo tridy, ktera trait pouziva. Stejne funguje
+/// i pro vycty a zaznamy.
+pub struct Is {
+    pub(crate) value: Rc<dyn Expr>,
+    pub(crate) keyword: Token,
+    pub(crate) target: Rc<dyn Expr>,
+}
+
+impl Expr for Is {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let value = self.value.evaluate(Rc::clone(&env))?;
+        let result = match (self.target.evaluate(env)?, value) {
+            (Value::Class(class), Value::Instance(instance)) => is_subclass(&instance.class, &class),
+            (Value::Trait(used), Value::Instance(instance)) => uses_trait(&instance.class, &used),
+            (Value::Enum(owner), Value::Variant(variant)) => Rc::ptr_eq(&owner, &variant.owner),
+            (Value::RecordType(record), Value::Record(value)) => Rc::ptr_eq(&record, &value.record),
+            (Value::Class(_) | Value::Trait(_) | Value::Enum(_) | Value::RecordType(_), _) => false,
+            _ => {
+                return Err((
+                    String::from("Za 'is' musi byt trida, trait, vycet nebo zaznam."),
+                    self.keyword.clone(),
+                ))
+            }
+        };
+        Ok(Value::Bool(result))
+    }
+
+    fn check(&self, checker: &mut Checker) -> Type {
+        self.value.check(checker);
+        self.target.check(checker);
+        Type::Bool
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Is
+    }
+}
+
+pub const CHAIN_RECEIVER: &str = "?.";
+
+pub struct OptionalChain {
+    pub(crate) object: Rc<dyn Expr>,
+    pub(crate) chain: Rc<dyn Expr>,
+}
+
+impl Expr for OptionalChain {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        match self.object.evaluate(Rc::clone(&env))? {
+            Value::None => Ok(Value::None),
+            receiver => {
+                let chain_env = Rc::new(Environment::new_child(env));
+                chain_env.define(String::from(CHAIN_RECEIVER), receiver);
+                self.chain.evaluate(chain_env)
+            }
+        }
+    }
+
+    fn check(&self, checker: &mut Checker) -> Type {
+        let object = self.object.check(checker);
+        checker.begin_scope();
+        checker.declare(CHAIN_RECEIVER, object.required().clone());
+        let chain = self.chain.check(checker);
+        checker.end_scope();
+        checker.unify(&Type::Nil, &chain)
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::OptionalChain
+    }
+}
+
+pub struct Lambda {
+    pub(crate) keyword: Token,
+    pub(crate) parameters: Vec<Parameter>,
+    pub(crate) body: Vec<Rc<dyn Statement>>,
+    pub(crate) generator: bool,
+    pub(crate) return_type: Option<Annotation>,
+}
+
+impl Expr for Lambda {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let mut name = self.keyword.clone();
+        name.lexeme = format!("lambda@line {}", self.keyword.line);
+        let string = format!("<fn {}>", name.lexeme);
+        let closure = Rc::new(Environment::new_child(env));
+        Ok(Value::Function(Rc::new(make_function(
+            name,
+            &self.parameters,
+            &self.body,
+            string,
+            closure,
+            self.generator,
+            self.return_type.clone(),
+        ))))
+    }
+
+    fn check(&self, checker: &mut Checker) -> Type {
+        let signature = checker.signature(
+            "lambda",
+            &self.parameters,
+            self.return_type.as_ref(),
+            self.generator,
+        );
+        checker.function_body(&self.keyword, &signature, &self.body, self.generator);
+        Type::Function(Some(signature))
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Lambda
+    }
+}
+
+pub struct ListLiteral {
+    pub(crate) elements: Vec<Rc<dyn Expr>>,
+}
+
+impl Expr for ListLiteral {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let mut elements = Vec::with_capacity(self.elements.len());
+        for element in &self.elements {
+            elements.push(element.evaluate(Rc::clone(&env))?);
+        }
+        Ok(Value::List(Rc::new(RefCell::new(elements))))
+    }
+
+    fn check(&self, checker: &mut Checker) -> Type {
+        for element in &self.elements {
+            element.check(checker);
+        }
+        Type::List
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::List(self.elements.clone())
+    }
+}
+
+pub struct TupleLiteral {
+    pub(crate) elements: Vec<Rc<dyn Expr>>,
+}
+
+impl Expr for TupleLiteral {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let mut elements = Vec::with_capacity(self.elements.len());
+        for element in &self.elements {
+            elements.push(element.evaluate(Rc::clone(&env))?);
+        }
+        Ok(Value::Tuple(Rc::new(elements)))
+    }
+
+    fn check(&self, checker: &mut Checker) -> Type {
+        for element in &self.elements {
+            element.check(checker);
+        }
+        Type::Tuple
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Tuple(self.elements.clone())
+    }
+}
+
+pub struct SetLiteral {
+    pub(crate) brace: Token,
+    pub(crate) elements: Vec<Rc<dyn Expr>>,
+}
+
+impl Expr for SetLiteral {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let mut elements = Vec::with_capacity(self.elements.len());
+        for element in &self.elements {
+            elements.push(element.evaluate(Rc::clone(&env))?);
+        }
+        Ok(SetValue::from_values(elements, &self.brace)?.into_value())
+    }
+
+    fn check(&self, checker: &mut Checker) -> Type {
+        for element in &self.elements {
+            element.check(checker);
+        }
+        Type::Set
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::SetLiteral
+    }
+}
+
+pub struct RangeExpr {
+    pub(crate) start: Option<Rc<dyn Expr>>,
+    pub(crate) end: Option<Rc<dyn Expr>>,
+    pub(crate) step: Option<Rc<dyn Expr>>,
+    pub(crate) inclusive: bool,
+    pub(crate) op: Token,
+}
+
+impl Expr for RangeExpr {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let bound = |expr: &Option<Rc<dyn Expr>>| -> Result<Option<i64>, (String, Token)> {
+            match expr {
+                None => Ok(None),
+                Some(expr) => match expr.evaluate(Rc::clone(&env))? {
+                    Value::Int(a) => Ok(Some(a)),
+                    Value::None => Ok(None),
+                    other => Err((
+                        format!("Hranice rozsahu musi byt cele cislo, ne {}.", other),
+                        self.op.clone(),
+                    )),
+                },
+            }
+        };
+        let start = bound(&self.start)?;
+        let end = bound(&self.end)?;
+        let step = bound(&self.step)?.unwrap_or(1);
+        if step == 0 {
+            return Err((String::from("Krok rozsahu nesmi byt nula."), self.op.clone()));
+        }
+        if self.inclusive && end.is_none() {
+            return Err((
+                String::from("Rozsah s '..=' musi mit konec."),
+                self.op.clone(),
+            ));
+        }
+        Ok(Value::Range(RangeValue {
+            start,
+            end,
+            inclusive: self.inclusive,
+            step,
+        }))
+    }
+
+    fn check(&self, checker: &mut Checker) -> Type {
+        for bound in [&self.start, &self.end, &self.step].into_iter().flatten() {
+            let ty = bound.check(checker);
+            checker.expect(&Type::Optional(Box::new(Type::Int)), &ty, &self.op, || {
+                format!("Hranice rozsahu musi byt cele cislo, ne {}.", ty)
+            });
+        }
+        Type::Range
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Range
+    }
+}
+
+pub struct Index {
+    pub(crate) object: Rc<dyn Expr>,
+    pub(crate) bracket: Token,
+    pub(crate) index: Rc<dyn Expr>,
+}
+
+impl Expr for Index {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let object = self.object.evaluate(Rc::clone(&env))?;
+        let index = self.index.evaluate(env)?;
+        get_index(&object, &index, &self.bracket)
+    }
+
+    fn check(&self, checker: &mut Checker) -> Type {
+        let object = self.object.check(checker);
+        let index = self.index.check(checker);
+        checker.index(&object, &index, &self.bracket)
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Index(self.bracket.clone(), Rc::clone(&self.object), Rc::clone(&self.index))
+    }
+}
+
+pub struct SetIndex {
+    pub(crate) object: Rc<dyn Expr>,
+    pub(crate) bracket: Token,
+    pub(crate) index: Rc<dyn Expr>,
+    pub(crate) op: Option<Token>,
+    pub(crate) value: Rc<dyn Expr>,
+}
+
+impl Expr for SetIndex {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let object = self.object.evaluate(Rc::clone(&env))?;
+        let index = self.index.evaluate(Rc::clone(&env))?;
+        let value = match &self.op {
+            None => self.value.evaluate(env)?,
+            Some(op) => {
+                let current = get_index(&object, &index, &self.bracket)?;
+                let value = self.value.evaluate(env)?;
+                binary_operation(compound_operator(op), op.clone(), current, value)?
+            }
+        };
+        set_index(&object, &index, value.clone(), &self.bracket)?;
+        Ok(value)
+    }
+
+    fn check(&self, checker: &mut Checker) -> Type {
+        let object = self.object.check(checker);
+        let index = self.index.check(checker);
+        let mut value = self.value.check(checker);
+        if let Some(op) = &self.op {
+            let current = checker.index(&object, &index, &self.bracket);
+            value = checker.binary(&compound_operator(op), op, &current, &value);
+        }
+        checker.set_index(&object, &index, &self.bracket);
+        value
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::SetIndex
+    }
+}
+
+fn position(index: i64, length: usize, token: &Token) -> Result<usize, (String, Token)> {
+    let resolved = if index < 0 { index + length as i64 } else { index };
+    if resolved < 0 || resolved >= length as i64 {
+        Err((format!("Index {} je mimo rozsah.", index), token.clone()))
+    } else {
+        Ok(resolved as usize)
+    }
+}
+
+pub(crate) fn get_index(object: &Value, index: &Value, token: &Token) -> Result<Value, (String, Token)> {
+    match (object, index) {
+        (Value::Instance(instance), _) => match instance.call_special("__getitem__", vec![index.clone()]) {
+            Some(result) => result,
+            None => Err((
+                String::from("Indexovat lze jen seznamy, ntice a retezce."),
+                token.clone(),
+            )),
+        },
+        (Value::List(list), Value::Int(i)) => {
+            let list = list.borrow();
+            Ok(list[position(*i, list.len(), token)?].clone())
+        }
+        (Value::List(list), Value::Range(range)) => {
+            let list = list.borrow();
+            let indices = range.slice_indices(list.len()).map_err(|msg| (msg, token.clone()))?;
+            let slice = indices.into_iter().map(|i| list[i].clone()).collect();
+            Ok(Value::List(Rc::new(RefCell::new(slice))))
+        }
+        (Value::Tuple(items), Value::Int(i)) => Ok(items[position(*i, items.len(), token)?].clone()),
+        (Value::Tuple(items), Value::Range(range)) => {
+            let indices = range.slice_indices(items.len()).map_err(|msg| (msg, token.clone()))?;
+            Ok(Value::Tuple(Rc::new(indices.into_iter().map(|i| items[i].clone()).collect())))
+        }
+        (Value::String(string), Value::Int(i)) => {
+            let chars: Vec<char> = string.chars().collect();
+            Ok(Value::String(chars[position(*i, chars.len(), token)?].to_string()))
+        }
+        (Value::String(string), Value::Range(range)) => {
+            let chars: Vec<char> = string.chars().collect();
+            let indices = range.slice_indices(chars.len()).map_err(|msg| (msg, token.clone()))?;
+            Ok(Value::String(indices.into_iter().map(|i| chars[i]).collect()))
+        }
+        (Value::List(_) | Value::Tuple(_) | Value::String(_), _) => Err((
+            format!("Index musi byt cele cislo nebo rozsah, ne {}.", index),
+            token.clone(),
+        )),
+        _ => Err((
+            String::from("Indexovat lze jen seznamy, ntice a retezce."),
+            token.clone(),
+        )),
+    }
+}
+
+pub(crate) fn set_index(object: &Value, index: &Value, value: Value, token: &Token) -> Result<(), (String, Token)> {
+    match (object, index) {
+        (Value::Instance(instance), _) => match instance.call_special("__setitem__", vec![index.clone(), value]) {
+            Some(result) => result.map(|_| ()),
+            None => Err((
+                String::from("Indexovat lze jen seznamy, ntice a retezce."),
+                token.clone(),
+            )),
+        },
+        (Value::List(list), Value::Int(i)) => {
+            let mut list = list.borrow_mut();
+            let i = position(*i, list.len(), token)?;
+            list[i] = value;
+            Ok(())
+        }
+        (Value::List(_), _) => Err((
+            format!("Index musi byt cele cislo, ne {}.", index),
+            token.clone(),
+        )),
+        (Value::String(_), _) => Err((
+            String::from("Retezce nelze menit."),
+            token.clone(),
+        )),
+        (Value::Tuple(_), _) => Err((
+            String::from("Ntice nelze menit."),
+            token.clone(),
+        )),
+        _ => Err((
+            String::from("Indexovat lze jen seznamy, ntice a retezce."),
+            token.clone(),
+        )),
+    }
+}
+
 pub struct This {
     pub(crate) keyword: Token,
 }
@@ -305,6 +1295,10 @@ impl Expr for This {
         }
     }
 
+    fn check(&self, checker: &mut Checker) -> Type {
+        checker.this_type()
+    }
+
     fn kind(&self) -> Kind {
         Kind::This 
     }
@@ -340,19 +1334,138 @@ impl Expr for Super {
         }
     }
 
+    fn check(&self, _checker: &mut Checker) -> Type {
+        Type::Any
+    }
+
     fn kind(&self) -> Kind {
         Kind::Super 
     }
 }
 
-fn is_equal(val1: Value, val2: Value, invert: bool) -> Value {
-    if invert {
-        Value::Bool(val1 != val2)
+pub(crate) enum Numbers {
+    Int(i64, i64),
+    Float(f64, f64),
+    Big(BigInt, BigInt),
+}
+
+pub(crate) fn numbers(left: &Value, right: &Value) -> Option<Numbers> {
+    match (left, right) {
+        (Value::Int(a), Value::Int(b)) => Some(Numbers::Int(*a, *b)),
+        (Value::Int(a), Value::BigInt(b)) => Some(Numbers::Big(BigInt::from(*a), (**b).clone())),
+        (Value::BigInt(a), Value::Int(b)) => Some(Numbers::Big((**a).clone(), BigInt::from(*b))),
+        (Value::BigInt(a), Value::BigInt(b)) => Some(Numbers::Big((**a).clone(), (**b).clone())),
+        (Value::Number(a), Value::Number(b)) => Some(Numbers::Float(*a, *b)),
+        (a, b) => match (a.as_float(), b.as_float()) {
+            (Some(a), Some(b)) => Some(Numbers::Float(a, b)),
+            _ => None,
+        },
+    }
+}
+
+fn integer_op(
+    a: i64,
+    b: i64,
+    small: fn(i64, i64) -> Option<i64>,
+    big: fn(&BigInt, &BigInt) -> BigInt,
+) -> Value {
+    match small(a, b) {
+        Some(result) => Value::Int(result),
+        None => Value::integer(big(&BigInt::from(a), &BigInt::from(b))),
+    }
+}
+
+fn floor_div(a: i64, b: i64) -> Option<i64> {
+    let quotient = a.checked_div(b)?;
+    if a % b != 0 && ((a < 0) != (b < 0)) {
+        Some(quotient - 1)
     } else {
-        Value::Bool(val1 == val2)
+        Some(quotient)
+    }
+}
+
+fn compound_operator(op: &Token) -> TokenType {
+    match op.token_type {
+        TokenType::PlusEqual | TokenType::PlusPlus => TokenType::Plus,
+        TokenType::MinusEqual | TokenType::MinusMinus => TokenType::Minus,
+        TokenType::StarEqual => TokenType::Star,
+        TokenType::SlashEqual => TokenType::Slash,
+        TokenType::PercentEqual => TokenType::Percent,
+        _ => op.token_type.clone(),
     }
 }
 
+/// Nejvetsi pocet bitu vysledku posunu a umocneni. Vetsi cisla by se
+/// pocitala neunosne dlouho.
+const MAX_BITS: i64 = 1 << 24;
+
+fn floor_mod(a: i64, b: i64) -> Option<i64> {
+    let remainder = a.checked_rem(b)?;
+    if remainder != 0 && ((remainder < 0) != (b < 0)) {
+        Some(remainder + b)
+    } else {
+        Some(remainder)
+    }
+}
+
+fn bitwise(
+    left: Value,
+    right: Value,
+    token: Token,
+    small: fn(i64, i64) -> i64,
+    big: fn(&BigInt, &BigInt) -> BigInt,
+) -> Result<Value, (String, Token)> {
+    match numbers(&left, &right) {
+        Some(Numbers::Int(a, b)) => Ok(Value::Int(small(a, b))),
+        Some(Numbers::Big(a, b)) => Ok(Value::integer(big(&a, &b))),
+        _ => Err((
+            String::from("Bitove operace lze pouzit jen na cela cisla."),
+            token,
+        )),
+    }
+}
+
+/// Umocneni, ktere se nevejde do `i64`. Zaklady 0, 1 a -1 zustanou
+/// male pro jakykoliv exponent, ostatni maji omezenou velikost vysledku.
+fn power(base: BigInt, exponent: u64, token: Token) -> Result<Value, (String, Token)> {
+    if base.bits() > 1 && base.bits().saturating_mul(exponent) > MAX_BITS as u64 {
+        return Err((String::from("Vysledek umocneni je prilis velky."), token));
+    }
+    Ok(Value::integer(base.pow(exponent)))
+}
+
+fn shift(left: Value, right: Value, token: Token, right_shift: bool) -> Result<Value, (String, Token)> {
+    let bits = match right {
+        Value::Int(bits) if bits >= 0 => bits,
+        Value::Int(_) => {
+            return Err((String::from("Nelze posunout o zaporny pocet bitu."), token));
+        }
+        Value::BigInt(_) => {
+            return Err((String::from("Posun je prilis velky."), token));
+        }
+        _ => {
+            return Err((String::from("Posun musi byt cele cislo."), token));
+        }
+    };
+    let number = match left {
+        Value::Int(a) => BigInt::from(a),
+        Value::BigInt(a) => (*a).clone(),
+        _ => {
+            return Err((String::from("Posouvat lze jen cela cisla."), token));
+        }
+    };
+    if right_shift {
+        return match usize::try_from(bits) {
+            Ok(bits) => Ok(Value::integer(number.shr(bits))),
+            _ => Ok(Value::Int(if number < BigInt::zero() { -1 } else { 0 })),
+        };
+    }
+    if bits > MAX_BITS {
+        return Err((String::from("Posun je prilis velky."), token));
+    }
+    Ok(Value::integer(number.shl(bits as usize)))
+}
+
 pub fn is_truth(val: Value, invert: bool) -> Result<Value, (String, Token)> {
     match val {
         Value::Bool(a) => {
@@ -366,3 +1479,54 @@ pub fn is_truth(val: Value, invert: bool) -> Result<Value, (String, Token)> {
         _ => Ok(Value::Bool(true)),
     }
 }
+
+pub struct Match {
+    pub(crate) keyword: Token,
+    pub(crate) value: Rc<dyn Expr>,
+    pub(crate) arms: Vec<MatchArm<Rc<dyn Expr>>>,
+}
+
+impl Expr for Match {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let value = self.value.evaluate(Rc::clone(&env))?;
+        let (body, arm_environment) = select_arm(&self.keyword, &value, &self.arms, &env)?;
+        body.evaluate(arm_environment)
+    }
+
+    fn check(&self, checker: &mut Checker) -> Type {
+        self.value.check(checker);
+        checker
+            .match_arms(&self.arms, |body, checker| body.check(checker))
+            .unwrap_or(Type::Any)
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Match
+    }
+}
+
+pub struct DestructuringAssign {
+    pub(crate) pattern: Pattern,
+    pub(crate) equals: Token,
+    pub(crate) value: Rc<dyn Expr>,
+}
+
+impl Expr for DestructuringAssign {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let value = self.value.evaluate(Rc::clone(&env))?;
+        let mut bindings = Vec::new();
+        self.pattern.destructure(&value, &self.equals, &mut bindings)?;
+        for (name, bound) in bindings {
+            env.assign(&name, bound)?;
+        }
+        Ok(value)
+    }
+
+    fn check(&self, checker: &mut Checker) -> Type {
+        self.value.check(checker)
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::DestructuringAssign
+    }
+}
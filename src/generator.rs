@@ -0,0 +1,197 @@
+use crate::expressions::{is_truth, Expr};
+use crate::iteration::MoonIterator;
+use crate::moonenv::Environment;
+use crate::natives::native_method;
+use crate::patterns::select_arm;
+use crate::statements::{bind_loop_variables, Statement, StatementKind};
+use crate::token::Token;
+use crate::value::Value;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+/// Rozpracovany ramec generatoru. Misto rekurze pres `Statement::evaluate`
+/// si generator drzi zasobnik techto ramcu, aby mohl beh u `yield` prerusit
+/// a pri dalsim volani `next()` pokracovat tam, kde skoncil.
+enum Frame {
+    Block {
+        statements: Vec<Rc<dyn Statement>>,
+        index: usize,
+        env: Rc<Environment>,
+    },
+    While {
+        condition: Rc<dyn Expr>,
+        body: Rc<dyn Statement>,
+        env: Rc<Environment>,
+    },
+    ForIn {
+        names: Vec<Token>,
+        iterator: MoonIterator,
+        body: Rc<dyn Statement>,
+        env: Rc<Environment>,
+    },
+}
+
+pub struct Generator {
+    pub(crate) name: String,
+    frames: RefCell<Vec<Frame>>,
+    running: Cell<bool>,
+}
+
+impl Debug for Generator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Generator")
+            .field("name", &self.name)
+            .field("finished", &self.frames.borrow().is_empty())
+            .finish()
+    }
+}
+
+impl Generator {
+    pub fn new(name: String, body: Vec<Rc<dyn Statement>>, env: Rc<Environment>) -> Self {
+        Generator {
+            name,
+            frames: RefCell::new(vec![Frame::Block {
+                statements: body,
+                index: 0,
+                env,
+            }]),
+            running: Cell::new(false),
+        }
+    }
+
+    /// Pokracuje v behu az k dalsimu `yield`. Vraci `None`, kdyz generator skoncil
+    /// (dobehl nebo narazil na `return`), a uz nikdy nic dalsiho nevrati.
+    pub fn resume(&self, token: &Token) -> Result<Option<Value>, (String, Token)> {
+        if self.running.get() {
+            return Err((
+                format!("Generator {} uz bezi.", self.name),
+                token.clone(),
+            ));
+        }
+        let mut frames = self.frames.take();
+        self.running.set(true);
+        let result = run(&mut frames);
+        self.running.set(false);
+        if let Ok(Some(_)) = result {
+            *self.frames.borrow_mut() = frames;
+        }
+        result
+    }
+
+    pub fn get_property(self: &Rc<Self>, name: &Token) -> Result<Value, (String, Token)> {
+        match &*name.lexeme {
+            "next" => {
+                let generator = Rc::clone(self);
+                let token = name.clone();
+                Ok(native_method("next", &[], move |_arguments| {
+                    Ok(generator.resume(&token)?.unwrap_or(Value::None))
+                }))
+            }
+            _ => Err((
+                format!("Generator nema vlastnost '{}'.", name.lexeme),
+                name.clone(),
+            )),
+        }
+    }
+}
+
+fn run(frames: &mut Vec<Frame>) -> Result<Option<Value>, (String, Token)> {
+    while let Some(frame) = frames.last_mut() {
+        let (statement, env) = match frame {
+            Frame::Block {
+                statements,
+                index,
+                env,
+            } => match statements.get(*index) {
+                None => {
+                    frames.pop();
+                    continue;
+                }
+                Some(statement) => {
+                    *index += 1;
+                    (Rc::clone(statement), Rc::clone(env))
+                }
+            },
+            Frame::While {
+                condition,
+                body,
+                env,
+            } => {
+                if is_truth(condition.evaluate(Rc::clone(env))?, false)? != Value::Bool(true) {
+                    frames.pop();
+                    continue;
+                }
+                (Rc::clone(body), Rc::clone(env))
+            }
+            Frame::ForIn {
+                names,
+                iterator,
+                body,
+                env,
+            } => match iterator.next()? {
+                None => {
+                    frames.pop();
+                    continue;
+                }
+                Some(item) => {
+                    let loop_environment = Rc::new(Environment::new_child(Rc::clone(env)));
+                    bind_loop_variables(&loop_environment, names, item)?;
+                    (Rc::clone(body), loop_environment)
+                }
+            },
+        };
+
+        match statement.kind() {
+            StatementKind::Block(statements) => frames.push(Frame::Block {
+                statements,
+                index: 0,
+                env: Rc::new(Environment::new_child(env)),
+            }),
+            StatementKind::If(condition, then_branch, else_branch) => {
+                let branch = match is_truth(condition.evaluate(Rc::clone(&env))?, false)? {
+                    Value::Bool(true) => Some(then_branch),
+                    _ => else_branch,
+                };
+                if let Some(branch) = branch {
+                    frames.push(Frame::Block {
+                        statements: vec![branch],
+                        index: 0,
+                        env,
+                    });
+                }
+            }
+            StatementKind::While(condition, body) => frames.push(Frame::While {
+                condition,
+                body,
+                env,
+            }),
+            StatementKind::ForIn(keyword, names, iterable, body) => {
+                let iterator = MoonIterator::new(iterable.evaluate(Rc::clone(&env))?, &keyword)?;
+                frames.push(Frame::ForIn {
+                    names,
+                    iterator,
+                    body,
+                    env,
+                });
+            }
+            StatementKind::Match(keyword, value, arms) => {
+                let value = value.evaluate(Rc::clone(&env))?;
+                let (body, arm_environment) = select_arm(&keyword, &value, &arms, &env)?;
+                frames.push(Frame::Block {
+                    statements: vec![Rc::clone(body)],
+                    index: 0,
+                    env: arm_environment,
+                });
+            }
+            StatementKind::Yield(value) => return Ok(Some(value.evaluate(env)?)),
+            _ => {
+                if let Value::Return(_) = statement.evaluate(env)? {
+                    frames.clear();
+                }
+            }
+        }
+    }
+    Ok(None)
+}
@@ -1,40 +1,22 @@
 use crate::moonenv::Environment;
-use crate::value::{Callable, Value};
+use crate::natives::define_natives;
+use crate::value::Value;
 use crate::statements::Statement;
 use crate::token::Token;
-use crate::tokentype::TokenType;
-use std::cell::RefCell;
 use std::rc::Rc;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 pub struct Interpreter {
     envi: Rc<Environment>
 }
 
 impl Interpreter {
-    pub fn new() -> Self {
-        let env = Rc::new(Environment::new());
-        let callable = Callable {
-            arity: 0,
-            function: Rc::new(|_arguments, _env| {
-                Ok(Value::Number(
-                        SystemTime::now()
-                            .duration_since(UNIX_EPOCH)
-                            .expect("Cas jde pozpatku.")
-                            .as_secs_f64(),
-                    ))
-            }),
-            string: "<native fn>".to_string(),
-            name: Token {
-                token_type: TokenType::Identifier, 
-                lexeme: "clock".to_string(),
-                literal: Value::None,
-                line: 0,
-            },
-            environment: Rc::clone(&env),
-            is_initializer: RefCell::new(false),
+    pub fn new(strict_types: bool) -> Self {
+        let env = if strict_types {
+            Rc::new(Environment::new_strict())
+        } else {
+            Rc::new(Environment::new())
         };
-        env.define(String::from("clock"), Value::Function(Rc::new(callable)));
+        define_natives(&env);
         Interpreter {envi: env}
     }
 
@@ -44,6 +26,16 @@ impl Interpreter {
         }
     }
 
+    /// Jmena definovana v globalnim prostredi a jestli jsou konstantni.
+    pub fn globals(&self) -> Vec<(String, bool)> {
+        self.envi
+            .values
+            .borrow()
+            .keys()
+            .map(|name| (name.clone(), self.envi.is_constant(name)))
+            .collect()
+    }
+
     pub fn interpret(
         &mut self,
         statements: Vec<Rc<dyn Statement>>,
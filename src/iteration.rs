@@ -0,0 +1,99 @@
+use crate::generator::Generator;
+use crate::token::Token;
+use crate::value::{InstanceValue, RangeValue, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub enum MoonIterator {
+    List(Rc<RefCell<Vec<Value>>>, usize),
+    Chars(Vec<char>, usize),
+    Protocol(Rc<InstanceValue>, Token),
+    Range(RangeValue, i128),
+    Generator(Rc<Generator>, Token),
+}
+
+impl MoonIterator {
+    pub fn new(iterable: Value, token: &Token) -> Result<Self, (String, Token)> {
+        match iterable {
+            Value::List(a) => Ok(MoonIterator::List(a, 0)),
+            Value::Tuple(a) => Ok(MoonIterator::List(Rc::new(RefCell::new(a.to_vec())), 0)),
+            Value::Set(a) => Ok(MoonIterator::List(Rc::new(RefCell::new(a.borrow().items().to_vec())), 0)),
+            Value::String(a) => Ok(MoonIterator::Chars(a.chars().collect(), 0)),
+            Value::Range(range) => match range.start {
+                Some(start) => Ok(MoonIterator::Range(range, start as i128)),
+                None => Err((
+                    String::from("Rozsah bez zacatku nelze prochazet."),
+                    token.clone(),
+                )),
+            },
+            Value::Generator(generator) => Ok(MoonIterator::Generator(generator, token.clone())),
+            Value::Enum(owner) => MoonIterator::new(owner.variants(token)?, token),
+            Value::Instance(instance) => {
+                let iterator = match instance.class.find_method(String::from("iter")) {
+                    Some(_) => {
+                        let mut iter = token.clone();
+                        iter.lexeme = String::from("iter");
+                        match instance.get_value(&iter)? {
+                            Value::Function(callable) => callable.call(Vec::new(), Vec::new())?,
+                            _ => Value::Instance(instance),
+                        }
+                    }
+                    None => Value::Instance(instance),
+                };
+                match iterator {
+                    Value::Instance(iterator) => {
+                        if iterator.class.find_method(String::from("next")).is_none() {
+                            return Err((
+                                format!("Iterator {} nema metodu 'next'.", iterator.class.name),
+                                token.clone(),
+                            ));
+                        }
+                        let mut next = token.clone();
+                        next.lexeme = String::from("next");
+                        Ok(MoonIterator::Protocol(iterator, next))
+                    }
+                    other => MoonIterator::new(other, token),
+                }
+            }
+            other => Err((format!("Hodnota {} neni iterovatelna.", other), token.clone())),
+        }
+    }
+
+    pub fn next(&mut self) -> Result<Option<Value>, (String, Token)> {
+        match self {
+            MoonIterator::List(list, index) => {
+                let item = list.borrow().get(*index).cloned();
+                *index += 1;
+                Ok(item)
+            }
+            MoonIterator::Chars(chars, index) => {
+                let item = chars.get(*index).map(|c| Value::String(c.to_string()));
+                *index += 1;
+                Ok(item)
+            }
+            MoonIterator::Range(range, current) => {
+                let finished = match range.end.map(|end| end as i128) {
+                    None => false,
+                    Some(end) if range.step > 0 && range.inclusive => *current > end,
+                    Some(end) if range.step > 0 => *current >= end,
+                    Some(end) if range.inclusive => *current < end,
+                    Some(end) => *current <= end,
+                };
+                if finished || *current > i64::MAX as i128 || *current < i64::MIN as i128 {
+                    return Ok(None);
+                }
+                let item = Value::Int(*current as i64);
+                *current += range.step as i128;
+                Ok(Some(item))
+            }
+            MoonIterator::Generator(generator, token) => generator.resume(token),
+            MoonIterator::Protocol(iterator, next) => match iterator.get_value(next)? {
+                Value::Function(callable) => match callable.call(Vec::new(), Vec::new())? {
+                    Value::None => Ok(None),
+                    item => Ok(Some(item)),
+                },
+                _ => Err((String::from("'next' musi byt metoda."), next.clone())),
+            },
+        }
+    }
+}
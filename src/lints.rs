@@ -0,0 +1,115 @@
+use crate::token::Token;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lint {
+    Unused,
+    Shadowing,
+    TypeComparison,
+    AssignInCondition,
+    EmptyBlock,
+    CapturedThis,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Allow,
+    Warn,
+    Deny,
+}
+
+pub const LINTS: [Lint; 6] = [
+    Lint::Unused,
+    Lint::Shadowing,
+    Lint::TypeComparison,
+    Lint::AssignInCondition,
+    Lint::EmptyBlock,
+    Lint::CapturedThis,
+];
+
+impl Lint {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lint::Unused => "unused",
+            Lint::Shadowing => "shadowing",
+            Lint::TypeComparison => "type-comparison",
+            Lint::AssignInCondition => "assign-in-condition",
+            Lint::EmptyBlock => "empty-block",
+            Lint::CapturedThis => "captured-this",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Lint> {
+        LINTS.iter().copied().find(|lint| lint.name() == name)
+    }
+
+    /// Zastinovani je casto zamerne, proto se hlasi jen na vyzadani.
+    fn default_level(&self) -> Level {
+        match self {
+            Lint::Shadowing => Level::Allow,
+            _ => Level::Warn,
+        }
+    }
+}
+
+pub struct Warning {
+    pub(crate) lint: Lint,
+    pub(crate) token: Token,
+    pub(crate) message: String,
+}
+
+/// Nastaveni urovni varovani z prikazove radky (`--warn`, `--deny`, `--allow`)
+/// a z komentaru `// moon:allow(...)` ve zdrojovem kodu.
+#[derive(Default)]
+pub struct LintConfig {
+    levels: HashMap<Lint, Level>,
+    allowed_lines: HashMap<u64, Vec<String>>,
+}
+
+impl LintConfig {
+    /// Nastavi uroven jednomu varovani nebo vsem (`all`).
+    pub fn set(&mut self, name: &str, level: Level) -> Result<(), String> {
+        if name == "all" {
+            for lint in LINTS {
+                self.levels.insert(lint, level);
+            }
+            return Ok(());
+        }
+        match Lint::from_name(name) {
+            Some(lint) => {
+                self.levels.insert(lint, level);
+                Ok(())
+            }
+            None => Err(format!("Nezname varovani '{}'.", name)),
+        }
+    }
+
+    /// Radky, na kterych komentar `// moon:allow(...)` vypina varovani.
+    pub fn allow_lines(&mut self, allows: &[(u64, String)]) {
+        self.allowed_lines.clear();
+        for (line, name) in allows {
+            self.allowed_lines
+                .entry(*line)
+                .or_default()
+                .push(name.clone());
+        }
+    }
+
+    pub fn level(&self, warning: &Warning) -> Level {
+        let allowed = self
+            .allowed_lines
+            .get(&warning.token.line)
+            .is_some_and(|names| {
+                names
+                    .iter()
+                    .any(|name| name == "all" || name == warning.lint.name())
+            });
+        if allowed {
+            return Level::Allow;
+        }
+        match self.levels.get(&warning.lint) {
+            Some(level) => *level,
+            None => warning.lint.default_level(),
+        }
+    }
+}
@@ -1,4 +1,8 @@
+mod bigint;
+mod checker;
+mod enums;
 mod expressions;
+mod generator;
 mod moon;
 mod tokentype;
 mod token;
@@ -7,23 +11,63 @@ mod value;
 mod moonenv;
 mod statements;
 mod interpreter;
+mod iteration;
+mod lints;
+mod natives;
 mod parser;
+mod patterns;
+mod records;
+mod sets;
+mod types;
 
 use std::env;
 
-use crate::moon::Moon;
+use crate::lints::Level;
+use crate::moon::{Moon, Options};
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    let mut moon: Moon = Moon::new();
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let check = args.first().is_some_and(|arg| arg == "check");
+    if check {
+        args.remove(0);
+    }
+    let mut options = Options::default();
+    let mut paths: Vec<&String> = Vec::new();
+    for arg in &args {
+        let lint = if let Some(name) = arg.strip_prefix("--warn=") {
+            Some((name, Level::Warn))
+        } else if let Some(name) = arg.strip_prefix("--deny=") {
+            Some((name, Level::Deny))
+        } else {
+            arg.strip_prefix("--allow=").map(|name| (name, Level::Allow))
+        };
+        match (arg.as_str(), lint) {
+            (_, Some((name, level))) => {
+                if let Err(msg) = options.lints.set(name, level) {
+                    println!("{}", msg);
+                    std::process::exit(64);
+                }
+            }
+            ("--sealed", None) => options.sealed = true,
+            ("--strict-types", None) => options.strict_types = true,
+            (flag, None) if flag.starts_with("--") => usage(),
+            _ => paths.push(arg),
+        }
+    }
+    let mut moon: Moon = Moon::new(options);
 
-    if args.len() > 2 {
-        println!("Pouziti: moon [skript].");
-        std::process::exit(64);
-    } else if args.len() == 2 {
-        let source: &String = &args[1];
-        moon.run_file(source);
+    if paths.len() > 1 || (check && paths.is_empty()) {
+        usage();
+    } else if check {
+        moon.check_file(paths[0]);
+    } else if paths.len() == 1 {
+        moon.run_file(paths[0]);
     } else {
         moon.run_prompt();
     }
 }
+
+fn usage() {
+    println!("Pouziti: moon [check] [--sealed] [--strict-types] [--warn=varovani] [--deny=varovani] [--allow=varovani] [skript].");
+    std::process::exit(64);
+}
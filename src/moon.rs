@@ -1,25 +1,39 @@
 use std::fs;
 use std::io;
 use std::io::Write;
+use std::rc::Rc;
 
+use crate::checker::Checker;
 use crate::interpreter::Interpreter;
+use crate::lints::{Level, LintConfig, Warning};
 use crate::parser::Parser;
 use crate::scanner::Scanner;
+use crate::statements::Statement;
 use crate::token::Token;
 use crate::tokentype::TokenType;
 
+/// Nastaveni z prikazove radky.
+#[derive(Default)]
+pub struct Options {
+    pub(crate) sealed: bool,
+    pub(crate) strict_types: bool,
+    pub(crate) lints: LintConfig,
+}
+
 pub struct Moon {
     had_error: bool,
     had_runtime_error: bool,
     interpreter: Interpreter,
+    options: Options,
 }
 
 impl Moon {
-    pub fn new() -> Self {
+    pub fn new(options: Options) -> Self {
         Moon {
         	had_error: false,
         	had_runtime_error: false,
-        	interpreter: Interpreter::new(), 
+        	interpreter: Interpreter::new(options.strict_types), 
+        	options,
         }
     }
 
@@ -54,6 +68,32 @@ impl Moon {
     }
 
     pub fn run(&mut self, source: String, quit_on_error: bool) {
+        let statements = match self.parse(source, quit_on_error) {
+        	Some(statements) => statements,
+        	None => return,
+        };
+        match self.interpreter.interpret(statements) {
+        	Ok(_) => {}
+        	Err((msg, token)) => self.runtime_error((String::from(msg), token.clone())),
+        }
+    }
+
+    /// `moon check`: program se jen zparsuje a zkontroluji se typy, nespousti se.
+    pub fn check_file(&mut self, path: &String) {
+        let source = fs::read_to_string(path).unwrap();
+        if let Some(statements) = self.parse(source, true) {
+        	for (token, msg) in Checker::new().check(&statements) {
+        		self.error_parse(&token, &msg);
+        	}
+        }
+        if self.had_error {
+            std::process::exit(65);
+        }
+    }
+
+    /// Zparsuje zdrojovy kod a nahlasi chyby a varovani. Vraci `None`,
+    /// pokud se program kvuli chybam nema spustit.
+    fn parse(&mut self, source: String, quit_on_error: bool) -> Option<Vec<Rc<dyn Statement>>> {
         let mut scanner = Scanner::new(source);
         let tokens: Vec<Token> = match scanner.scan_tokens() {
         	Ok(a) => a,
@@ -63,20 +103,22 @@ impl Moon {
         	}
         };
         if quit_on_error && (self.had_error || self.had_runtime_error) {
-        	return;
+        	return None;
         }
-        let mut parser = Parser::new(tokens);
+        self.options.lints.allow_lines(&scanner.allows);
+        let mut parser = Parser::new(tokens, self.options.sealed);
+        parser.declare_globals(self.interpreter.globals());
         let (statements, errors) = parser.parse();
         for (token, msg) in errors {
         	self.error_parse(&token, &*msg);
         }
-        if quit_on_error && (self.had_error || self.had_runtime_error) {
-        	return;
+        for warning in parser.take_warnings() {
+        	self.warning(&warning);
         }
-        match self.interpreter.interpret(statements) {
-        	Ok(_) => {}
-        	Err((msg, token)) => self.runtime_error((String::from(msg), token.clone())),
+        if quit_on_error && (self.had_error || self.had_runtime_error) {
+        	return None;
         }
+        Some(statements)
     }
 
     pub fn error(&mut self, line: u64, message: String) {
@@ -100,6 +142,29 @@ impl Moon {
     	}
     }
 
+    pub fn warning(&mut self, warning: &Warning) {
+    	let kind = match self.options.lints.level(warning) {
+    		Level::Allow => return,
+    		Level::Warn => "Warning",
+    		Level::Deny => {
+    			self.had_error = true;
+    			"Error"
+    		}
+    	};
+    	let location = match warning.token.token_type {
+    		TokenType::EOF => String::from("at end"),
+    		_ => format!("at '{}'", warning.token.lexeme),
+    	};
+    	eprintln!(
+    		"[line {}] {} {}: {} ({})",
+    		warning.token.line,
+    		kind,
+    		location,
+    		warning.message,
+    		warning.lint.name()
+    	);
+    }
+
     pub fn runtime_error(&mut self, error: (String, Token)) {
     	let (msg, token) = error;
     	eprintln!("{}\n[line {}]", msg, token.line);
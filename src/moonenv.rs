@@ -1,13 +1,19 @@
 use crate::value::Value;
 use crate::token::Token;
+use crate::types::Type;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
 #[derive(Debug)]
 pub struct Environment {
     pub(crate) enclosing: Option<Rc<Environment>>,
     pub(crate) values: RefCell<HashMap<String, Value>>,
+    pub(crate) constants: RefCell<HashSet<String>>,
+    pub(crate) types: RefCell<HashMap<String, Type>>,
+    /// Jestli se maji za behu kontrolovat anotace typu (`--strict-types`).
+    /// Vnorena prostredi nastaveni dedi od nadrazeneho.
+    pub(crate) strict_types: bool,
 }
 
 impl Clone for Environment {
@@ -15,11 +21,17 @@ impl Clone for Environment {
         Environment {
             enclosing: self.enclosing.clone(),
             values: self.values.clone(),
+            constants: self.constants.clone(),
+            types: self.types.clone(),
+            strict_types: self.strict_types,
         }
     }
 
     fn clone_from(&mut self, source: &Self) {
         self.values = source.values.clone();
+        self.constants = source.constants.clone();
+        self.types = source.types.clone();
+        self.strict_types = source.strict_types;
         self.enclosing = source.enclosing.clone();
     }
 }
@@ -29,20 +41,55 @@ impl Environment {
         Environment {
             enclosing: None,
             values: RefCell::new(HashMap::new()),
+            constants: RefCell::new(HashSet::new()),
+            types: RefCell::new(HashMap::new()),
+            strict_types: false,
+        }
+    }
+
+    pub fn new_strict() -> Self {
+        Environment {
+            strict_types: true,
+            ..Environment::new()
         }
     }
 
     pub fn new_child(env: Rc<Environment>) -> Self {
         Environment {
+            strict_types: env.strict_types,
             enclosing: Some(env.clone()),
             values: RefCell::new(HashMap::new()),
+            constants: RefCell::new(HashSet::new()),
+            types: RefCell::new(HashMap::new()),
         }
     }
 
     pub(crate) fn define(&self, key: String, value: Value) {
+        self.constants.borrow_mut().remove(&key);
+        self.types.borrow_mut().remove(&key);
+        self.values.borrow_mut().insert(key, value);
+    }
+
+    /// Definuje promennou s anotaci typu. Hodnota se zkontroluje hned
+    /// a pak pri kazdem prirazeni.
+    pub(crate) fn define_typed(&self, name: &Token, value: Value, ty: Type) -> Result<(), (String, Token)> {
+        check_variable(name, &ty, &value)?;
+        self.define(name.lexeme.clone(), value);
+        self.types.borrow_mut().insert(name.lexeme.clone(), ty);
+        Ok(())
+    }
+
+    pub(crate) fn define_constant(&self, key: String, value: Value) {
+        self.constants.borrow_mut().insert(key.clone());
+        self.types.borrow_mut().remove(&key);
         self.values.borrow_mut().insert(key, value);
     }
 
+    /// Jestli je jmeno konstantou primo v tomto prostredi (ne v nadrazenych).
+    pub(crate) fn is_constant(&self, key: &str) -> bool {
+        self.constants.borrow().contains(key)
+    }
+
     pub(crate) fn get(&self, name: &Token) -> Result<Value, String> {
         match self.values.borrow_mut().get(&*name.lexeme) {
             None => match &self.enclosing {
@@ -66,6 +113,13 @@ impl Environment {
     pub(crate) fn assign(&self, name: &Token, value: Value) -> Result<(), (String, Token)> {
         let lexeme = &*name.lexeme;
         if self.values.borrow_mut().contains_key(lexeme) {
+            if self.is_constant(lexeme) {
+                let msg = format!("Nelze prirazovat do konstanty '{}'.", name.lexeme);
+                return Err((msg, name.clone()));
+            }
+            if let Some(ty) = self.types.borrow().get(lexeme) {
+                check_variable(name, ty, &value)?;
+            }
             self.values.borrow_mut().insert(String::from(lexeme), value);
             return Ok(());
         }
@@ -78,3 +132,18 @@ impl Environment {
         }
     }
 }
+
+pub(crate) fn check_variable(name: &Token, ty: &Type, value: &Value) -> Result<(), (String, Token)> {
+    if ty.matches(value) {
+        return Ok(());
+    }
+    Err((
+        format!(
+            "Promenna '{}' ma typ {}, ale hodnota je {}.",
+            name.lexeme,
+            ty,
+            Type::describe(value)
+        ),
+        name.clone(),
+    ))
+}
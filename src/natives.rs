@@ -0,0 +1,232 @@
+use crate::bigint::BigInt;
+use crate::expressions::{get_property, Expr, Literal};
+use crate::iteration::MoonIterator;
+use crate::moonenv::Environment;
+use crate::token::Token;
+use crate::tokentype::TokenType;
+use crate::types::Type;
+use crate::sets::SetValue;
+use crate::value::{min_arity, Callable, Class, Parameter, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub fn define_natives(env: &Rc<Environment>) {
+    define_native(env, "clock", &[], |_arguments| {
+        Ok(Value::Number(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Cas jde pozpatku.")
+                .as_secs_f64(),
+        ))
+    });
+    define_native(env, "int", &["value"], |arguments| match &arguments[0] {
+        Value::Int(_) | Value::BigInt(_) => Ok(arguments[0].clone()),
+        Value::Number(a) => match BigInt::from_f64(*a) {
+            Some(number) => Ok(Value::integer(number)),
+            None => Err(format!("Cislo {} nelze prevest na cele cislo.", a)),
+        },
+        Value::String(a) => match BigInt::parse(a.trim()) {
+            Some(number) => Ok(Value::integer(number)),
+            None => Err(format!("Retezec \"{}\" neni cele cislo.", a)),
+        },
+        Value::Bool(a) => Ok(Value::Int(*a as i64)),
+        other => Err(format!("Hodnotu {} nelze prevest na cele cislo.", other)),
+    });
+    define_native(env, "float", &["value"], |arguments| match &arguments[0] {
+        Value::Int(_) | Value::Number(_) | Value::BigInt(_) => {
+            Ok(Value::Number(arguments[0].as_float().unwrap()))
+        }
+        Value::String(a) => match a.trim().parse::<f64>() {
+            Ok(number) => Ok(Value::Number(number)),
+            Err(_) => Err(format!("Retezec \"{}\" neni cislo.", a)),
+        },
+        other => Err(format!("Hodnotu {} nelze prevest na desetinne cislo.", other)),
+    });
+    define_native(env, "len", &["value"], |arguments| match &arguments[0] {
+        Value::String(a) => Ok(Value::Int(a.chars().count() as i64)),
+        Value::List(a) => Ok(Value::Int(a.borrow().len() as i64)),
+        Value::Tuple(a) => Ok(Value::Int(a.len() as i64)),
+        Value::Set(a) => Ok(Value::Int(a.borrow().items().len() as i64)),
+        Value::Instance(a) => match a.call_special("__len__", Vec::new()) {
+            Some(result) => result.map_err(|(msg, _)| msg),
+            None => Err(format!("Hodnota {} nema delku.", arguments[0])),
+        },
+        other => Err(format!("Hodnota {} nema delku.", other)),
+    });
+    define_native(env, "set", &["values?"], |arguments| {
+        let token = identifier("set");
+        let mut values = Vec::new();
+        if arguments[0] != Value::None {
+            let mut iterator = MoonIterator::new(arguments[0].clone(), &token).map_err(|(msg, _)| msg)?;
+            while let Some(value) = iterator.next().map_err(|(msg, _)| msg)? {
+                values.push(value);
+            }
+        }
+        let set = SetValue::from_values(values, &token).map_err(|(msg, _)| msg)?;
+        Ok(set.into_value())
+    });
+    define_reflection(env);
+}
+
+/// Funkce pro zjistovani, co je hodnota zac: typ, trida, pole a metody.
+fn define_reflection(env: &Rc<Environment>) {
+    define_native(env, "type", &["value"], |arguments| match &arguments[0] {
+        Value::Class(_) => Ok(Value::String(String::from("Class"))),
+        Value::Trait(_) => Ok(Value::String(String::from("Trait"))),
+        Value::Enum(_) => Ok(Value::String(String::from("Enum"))),
+        Value::RecordType(_) => Ok(Value::String(String::from("Record"))),
+        other => Ok(Value::String(Type::describe(other))),
+    });
+    define_native(env, "classOf", &["value"], |arguments| match &arguments[0] {
+        Value::Instance(instance) => Ok(Value::Class(Rc::clone(&instance.class))),
+        _ => Ok(Value::None),
+    });
+    define_native(env, "superclass", &["class"], |arguments| match &arguments[0] {
+        Value::Class(class) => Ok(class
+            .super_class
+            .as_ref()
+            .map_or(Value::None, |super_class| Value::Class(Rc::clone(super_class)))),
+        other => Err(format!("Hodnota {} neni trida.", other)),
+    });
+    define_native(env, "fields", &["object"], |arguments| match &arguments[0] {
+        Value::Instance(instance) => Ok(names(instance.fields.borrow().keys().cloned().collect())),
+        Value::Record(value) => Ok(names(value.record.field_names().map(String::from).collect())),
+        other => Err(format!("Hodnota {} nema pole.", other)),
+    });
+    define_native(env, "methods", &["class"], |arguments| match &arguments[0] {
+        Value::Class(class) => Ok(names(method_names(class))),
+        Value::Instance(instance) => Ok(names(method_names(&instance.class))),
+        other => Err(format!("Hodnota {} nema metody.", other)),
+    });
+    define_native(env, "arity", &["function"], |arguments| match &arguments[0] {
+        Value::Function(callable) => Ok(Value::Int(min_arity(&callable.parameters) as i64)),
+        Value::RecordType(record) => Ok(Value::Int(min_arity(&record.parameters) as i64)),
+        Value::Class(class) => Ok(Value::Int(
+            class.initializer().map_or(0, |init| min_arity(&init.parameters)) as i64,
+        )),
+        other => Err(format!("Hodnota {} neni funkce.", other)),
+    });
+    define_native(env, "hasattr", &["object", "name"], |arguments| {
+        let name = property_name(&arguments[1])?;
+        let found = match &arguments[0] {
+            Value::Instance(instance) => {
+                instance.fields.borrow().contains_key(&name.lexeme)
+                    || instance.class.find_method(name.lexeme.clone()).is_some()
+                    || instance.class.find_accessor(&name.lexeme, false).is_some()
+            }
+            Value::Class(class) => class.find_static(&name.lexeme).is_some(),
+            Value::Record(value) => value.field(&name.lexeme).is_some() || name.lexeme == "with",
+            _ => false,
+        };
+        Ok(Value::Bool(found))
+    });
+    define_native(env, "getattr", &["object", "name"], |arguments| {
+        let name = property_name(&arguments[1])?;
+        get_property(&arguments[0], &name).map_err(|(msg, _)| msg)
+    });
+    define_native(env, "setattr", &["object", "name", "value"], |arguments| {
+        let name = property_name(&arguments[1])?;
+        match &arguments[0] {
+            Value::Instance(instance) => {
+                instance.set_value(&name, arguments[2].clone()).map_err(|(msg, _)| msg)?;
+                Ok(arguments[2].clone())
+            }
+            _ => Err(String::from("Jen instance maji pole.")),
+        }
+    });
+}
+
+fn property_name(value: &Value) -> Result<Token, String> {
+    match value {
+        Value::String(name) => Ok(identifier(name)),
+        other => Err(format!("Jmeno vlastnosti musi byt retezec, ne {}.", other)),
+    }
+}
+
+/// Jmena metod tridy vcetne zdedenych.
+fn method_names(class: &Class) -> Vec<String> {
+    let mut names: Vec<String> = class.methods.borrow().keys().cloned().collect();
+    if let Some(super_class) = &class.super_class {
+        names.extend(method_names(super_class));
+    }
+    names
+}
+
+/// Seznam retezcu, serazeny a bez opakovani, aby vypis nezavisel na poradi v `HashMap`.
+fn names(mut names: Vec<String>) -> Value {
+    names.sort();
+    names.dedup();
+    let list = names.into_iter().map(Value::String).collect();
+    Value::List(Rc::new(RefCell::new(list)))
+}
+
+fn define_native(
+    env: &Rc<Environment>,
+    name: &str,
+    parameters: &[&str],
+    native: fn(&[Value]) -> Result<Value, String>,
+) {
+    env.define(name.to_string(), native_function(name, parameters, native));
+}
+
+pub(crate) fn native_function(
+    name: &str,
+    parameters: &[&str],
+    native: impl Fn(&[Value]) -> Result<Value, String> + 'static,
+) -> Value {
+    let error_token = identifier(name);
+    native_method(name, parameters, move |arguments| {
+        native(arguments).map_err(|msg| (msg, error_token.clone()))
+    })
+}
+
+/// Jako `native_function`, ale chyba si nese vlastni token, takze muze ukazat
+/// na misto v programu, kde vznikla. Parametr s `?` na konci je nepovinny
+/// a kdyz chybi, je `nil`.
+pub(crate) fn native_method(
+    name: &str,
+    parameters: &[&str],
+    native: impl Fn(&[Value]) -> Result<Value, (String, Token)> + 'static,
+) -> Value {
+    Value::Function(Rc::new(native_callable(name, parameters, native)))
+}
+
+/// Funkce z `native_method` pred zabalenim do hodnoty, aby ji slo jeste
+/// upravit, treba jak se vypisuje.
+pub(crate) fn native_callable(
+    name: &str,
+    parameters: &[&str],
+    native: impl Fn(&[Value]) -> Result<Value, (String, Token)> + 'static,
+) -> Callable {
+    let token = identifier(name);
+    Callable {
+        parameters: parameters
+            .iter()
+            .map(|parameter| {
+                let optional = parameter.strip_suffix('?');
+                Parameter {
+                    name: identifier(optional.unwrap_or(parameter)),
+                    default: optional.map(|_| Rc::new(Literal { value: Value::None }) as Rc<dyn Expr>),
+                    rest: false,
+                    annotation: None,
+                }
+            })
+            .collect(),
+        function: Rc::new(move |arguments, _env| native(&arguments)),
+        string: "<native fn>".to_string(),
+        name: token,
+        environment: Rc::new(Environment::new()),
+        is_initializer: RefCell::new(false),
+        return_type: None,
+    }
+}
+
+fn identifier(name: &str) -> Token {
+    Token {
+        token_type: TokenType::Identifier,
+        lexeme: name.to_string(),
+        literal: Value::None,
+        line: 0,
+    }
+}
@@ -1,26 +1,83 @@
 use crate::expressions::*;
-use crate::value::Value;
+use crate::lints::{Lint, Warning};
+use crate::patterns::{MatchArm, Pattern};
+use crate::value::{Field, Parameter, Value};
 use crate::statements::*;
 use crate::token::Token;
 use crate::tokentype::TokenType;
+use crate::types::{Annotation, Type};
+use std::collections::HashMap;
 use std::rc::Rc;
 
+/// Co parser vi o jmenu deklarovanem v nekterem bloku.
+struct Binding {
+    token: Token,
+    constant: bool,
+    ready: bool,
+    used: bool,
+    parameter: bool,
+    /// Jmeno z predchoziho radku REPL, nova deklarace ho muze nahradit.
+    earlier: bool,
+}
+
+impl Binding {
+    fn new(token: &Token, constant: bool) -> Self {
+        Binding {
+            token: token.clone(),
+            constant,
+            ready: true,
+            used: false,
+            parameter: false,
+            earlier: false,
+        }
+    }
+}
+
+/// Co z funkce nebo lambdy nacte `Parser::function_parts`.
+struct FunctionParts {
+    parameters: Vec<Parameter>,
+    return_type: Option<Annotation>,
+    body: Vec<Rc<dyn Statement>>,
+    generator: bool,
+}
+
 pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
     in_a_class: bool,
     in_an_init: bool,
     in_a_subclass: bool,
+    in_a_function: bool,
+    in_a_generator: bool,
+    in_a_static: bool,
+    scopes: Vec<HashMap<String, Binding>>,
+    unresolved: Vec<(Token, usize)>,
+    sealed: bool,
+    errors: Vec<(Token, String)>,
+    warnings: Vec<Warning>,
+    in_a_lambda: bool,
+    /// Index bloku s parametry prave parsovane funkce, 0 mimo funkce.
+    function_scope: usize,
 }
 
 impl Parser {
-    pub fn new(tokens: Vec<Token>) -> Self {
+    pub fn new(tokens: Vec<Token>, sealed: bool) -> Self {
         Parser {
             tokens,
             current: 0,
             in_a_class: false,
             in_an_init: false,
             in_a_subclass: false,
+            in_a_function: false,
+            in_a_generator: false,
+            in_a_static: false,
+            scopes: vec![HashMap::new()],
+            unresolved: Vec::new(),
+            sealed,
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            in_a_lambda: false,
+            function_scope: 0,
         }
     }
 
@@ -30,9 +87,19 @@ impl Parser {
         while !self.is_at_end() {
             match self.declaration() {
                 Ok(statement) => statements.push(statement),
-                Err((msg, token)) => errors.push((token.clone(), msg)),
+                Err((msg, token)) => {
+                    self.scopes.truncate(1);
+                    errors.push((token.clone(), msg))
+                }
+            }
+        }
+        for (name, _) in std::mem::take(&mut self.unresolved) {
+            if !self.scopes[0].contains_key(&name.lexeme) {
+                self.error(format!("Nedeklarovana promenna '{}'.", name.lexeme), &name);
             }
         }
+        errors.append(&mut self.errors);
+        errors.sort_by_key(|(token, _)| token.line);
         (statements, errors)
     }
 
@@ -58,6 +125,13 @@ impl Parser {
         !self.is_at_end() && (self.peek().token_type == ttype)
     }
 
+    fn check_next(&self, ttype: TokenType) -> bool {
+        match self.tokens.get(self.current + 1) {
+            None => false,
+            Some(token) => token.token_type == ttype,
+        }
+    }
+
     fn advance(&mut self) -> &Token {
         if !self.is_at_end() {
             self.current = self.current + 1;
@@ -87,13 +161,18 @@ impl Parser {
             
             match self.peek().token_type {
                 TokenType::Class
+                | TokenType::Const
+                | TokenType::Enum
                 | TokenType::For
                 | TokenType::Fun
                 | TokenType::If
                 | TokenType::Print
+                | TokenType::Record
                 | TokenType::Return
+                | TokenType::Trait
                 | TokenType::Var
-                | TokenType::While => return,
+                | TokenType::While
+                | TokenType::Yield => return,
                 _ => {}
             }
             
@@ -101,6 +180,189 @@ impl Parser {
         }
     }
     
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    /// Zavre blok. Odkazy z nej, ktere zatim nesly dohledat, se jeste zkusi
+    /// najit mezi jmeny deklarovanymi v bloku az po nich, jinak se presunou
+    /// do nadrazeneho bloku. Nakonec se ohlasi nepouzita jmena.
+    fn end_scope(&mut self) {
+        let depth = self.scopes.len();
+        let mut scope = self.scopes.pop().expect("Chybi blok.");
+        self.unresolved.retain_mut(|(name, reference_depth)| {
+            if *reference_depth < depth {
+                return true;
+            }
+            *reference_depth = depth - 1;
+            match scope.get_mut(&name.lexeme) {
+                Some(binding) => {
+                    binding.used = true;
+                    false
+                }
+                None => true,
+            }
+        });
+        let mut unused: Vec<Binding> = scope
+            .into_values()
+            .filter(|binding| !binding.used && !binding.token.lexeme.starts_with('_'))
+            .collect();
+        unused.sort_by_key(|binding| binding.token.line);
+        for binding in unused {
+            let message = if binding.parameter {
+                format!("Nepouzity parametr '{}'.", binding.token.lexeme)
+            } else {
+                format!("Nepouzita promenna '{}'.", binding.token.lexeme)
+            };
+            self.warn(Lint::Unused, message, &binding.token);
+        }
+    }
+
+    /// Zapamatuje si jmeno deklarovane v aktualnim bloku, aby slo uz pri
+    /// parsovani odhalit prirazeni do konstanty nebo opakovanou deklaraci.
+    fn declare(&mut self, name: &Token, constant: bool) {
+        self.insert_binding(Binding::new(name, constant));
+    }
+
+    /// Jako `declare`, ale jmeno zatim nejde cist, protoze se teprve
+    /// vyhodnocuje jeho inicializator. Hotove ho oznaci `define`.
+    fn declare_pending(&mut self, name: &Token, constant: bool) {
+        let mut binding = Binding::new(name, constant);
+        binding.ready = false;
+        self.insert_binding(binding);
+    }
+
+    /// Parametr jde cist az po jeho vychozi hodnote, oznaci ho `define`.
+    fn declare_parameter(&mut self, name: &Token) {
+        let mut binding = Binding::new(name, false);
+        binding.parameter = true;
+        binding.ready = false;
+        self.insert_binding(binding);
+    }
+
+    fn define(&mut self, name: &Token) {
+        if let Some(binding) = self
+            .scopes
+            .last_mut()
+            .and_then(|scope| scope.get_mut(&name.lexeme))
+        {
+            binding.ready = true;
+        }
+    }
+
+    fn insert_binding(&mut self, binding: Binding) {
+        let name = binding.token.clone();
+        let constant = binding.constant;
+        let depth = self.scopes.len();
+        let shadowed = depth > 2
+            && self.scopes[1..depth - 1]
+                .iter()
+                .any(|scope| scope.contains_key(&name.lexeme));
+        let scope = self.scopes.last_mut().expect("Chybi globalni blok.");
+        let previous = scope.insert(name.lexeme.clone(), binding);
+        match previous {
+            Some(previous) if previous.earlier && previous.constant && !constant => self.error(
+                format!("Nelze znovu deklarovat konstantu '{}'.", name.lexeme),
+                &name,
+            ),
+            Some(previous) if previous.earlier => {}
+            Some(previous) if previous.constant => self.error(
+                format!("Konstanta '{}' uz je v tomto bloku deklarovana.", name.lexeme),
+                &name,
+            ),
+            Some(_) if depth > 1 => self.error(
+                format!("Promenna '{}' uz je v tomto bloku deklarovana.", name.lexeme),
+                &name,
+            ),
+            None if shadowed => self.warn(
+                Lint::Shadowing,
+                format!("Promenna '{}' zastinuje promennou z nadrazeneho bloku.", name.lexeme),
+                &name,
+            ),
+            _ => {}
+        }
+    }
+
+    fn lookup(&mut self, name: &Token) -> Option<(usize, &mut Binding)> {
+        self.scopes
+            .iter_mut()
+            .enumerate()
+            .rev()
+            .find_map(|(depth, scope)| scope.get_mut(&name.lexeme).map(|binding| (depth, binding)))
+    }
+
+    /// Zkontroluje cteni promenne. Jmeno, ktere zatim neni deklarovane,
+    /// se odlozi; chyba se ohlasi, jen pokud se deklarace nenajde vubec.
+    fn resolve(&mut self, name: &Token) {
+        if name.lexeme == "_" {
+            return;
+        }
+        let depth = self.scopes.len();
+        let function_scope = self.function_scope;
+        // Telo vnorene funkce nebo lambdy se spusti az po inicializaci.
+        let reading_own_initializer = match self.lookup(name) {
+            Some((depth, binding)) => {
+                binding.used = true;
+                depth > 0 && !binding.ready && depth >= function_scope
+            }
+            None => {
+                self.unresolved.push((name.clone(), depth));
+                false
+            }
+        };
+        if reading_own_initializer {
+            self.error(
+                format!("Nelze cist lokalni promennou '{}' v jejim vlastnim inicializatoru.", name.lexeme),
+                name,
+            );
+        }
+    }
+
+    fn check_assignable(&mut self, name: &Token) {
+        let constant = matches!(self.lookup(name), Some((_, binding)) if binding.constant);
+        if constant {
+            self.error(
+                format!("Nelze prirazovat do konstanty '{}'.", name.lexeme),
+                name,
+            );
+        }
+    }
+
+    /// Jmena, ktera uz existuji pred parsovanim (vestavene funkce a v REPL
+    /// vse, co se definovalo na predchozich radcich).
+    pub(crate) fn declare_globals(&mut self, globals: Vec<(String, bool)>) {
+        for (name, constant) in globals {
+            let token = Token {
+                token_type: TokenType::Identifier,
+                lexeme: name.clone(),
+                literal: Value::None,
+                line: 0,
+            };
+            let mut binding = Binding::new(&token, constant);
+            binding.earlier = true;
+            self.scopes[0].insert(name, binding);
+        }
+    }
+
+    /// Nahlasi chybu, po ktere muze parsovani normalne pokracovat.
+    fn error(&mut self, msg: String, token: &Token) {
+        self.errors.push((token.clone(), msg));
+    }
+
+    fn warn(&mut self, lint: Lint, message: String, token: &Token) {
+        self.warnings.push(Warning {
+            lint,
+            token: token.clone(),
+            message,
+        });
+    }
+
+    pub(crate) fn take_warnings(&mut self) -> Vec<Warning> {
+        let mut warnings = std::mem::take(&mut self.warnings);
+        warnings.sort_by_key(|warning| warning.token.line);
+        warnings
+    }
+
     fn expression(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
         self.assignment()
     }
@@ -108,10 +370,19 @@ impl Parser {
     fn declaration(&mut self) -> Result<Rc<dyn Statement>, (String, Token)> {
         if self.matching(&[TokenType::Class]) {
             self.class_declaration()
-        } else if self.matching(&[TokenType::Fun]) {
+        } else if self.matching(&[TokenType::Trait]) {
+            self.trait_declaration()
+        } else if self.matching(&[TokenType::Enum]) {
+            self.enum_declaration()
+        } else if self.matching(&[TokenType::Record]) {
+            self.record_declaration()
+        } else if self.check(TokenType::Fun) && self.check_next(TokenType::Identifier) {
+            self.advance();
             self.function("function")
         } else if self.matching(&[TokenType::Var]) {
-            self.var_declaration()
+            self.var_declaration(false)
+        } else if self.matching(&[TokenType::Const]) {
+            self.var_declaration(true)
         } else {
             let statement = self.statement();
             match statement {
@@ -129,6 +400,8 @@ impl Parser {
         let name = self
             .consume(TokenType::Identifier, String::from("Ocekavam nazev tridy."))?
             .clone();
+        let constant = self.sealed && self.scopes.len() == 1;
+        self.declare(&name, constant);
 
         let mut super_class: Option<Rc<dyn Expr>> = None;
         if self.matching(&[TokenType::Less]) {
@@ -137,19 +410,47 @@ impl Parser {
                 TokenType::Identifier,
                 String::from("Ocekavam nazev supertridy."),
             )?;
-            super_class = Some(Rc::new(Variable {
-                name: self.previous().clone(),
-            }));
+            let super_name = self.previous().clone();
+            self.resolve(&super_name);
+            super_class = Some(Rc::new(Variable { name: super_name }));
+        }
+
+        let mut traits: Vec<Token> = Vec::new();
+        if self.check(TokenType::Identifier) && self.peek().lexeme == "with" {
+            self.advance();
+            loop {
+                let used = self
+                    .consume(TokenType::Identifier, String::from("Ocekavam nazev traitu."))?
+                    .clone();
+                self.resolve(&used);
+                traits.push(used);
+                if !self.matching(&[TokenType::Comma]) {
+                    break;
+                }
+            }
         }
 
         self.consume(
             TokenType::LeftBrace,
             String::from("Ocekavam '{' pred zacatkem tela tridy."),
         )?;
-        let mut methods: Vec<Rc<dyn Statement>> = Vec::new();
+        let mut methods: Vec<(MethodKind, Rc<dyn Statement>)> = Vec::new();
+        let mut fields: Vec<Field> = Vec::new();
 
         while !self.check(TokenType::RightBrace) && !self.is_at_end() {
-            methods.push(self.function("method")?);
+            if self.check(TokenType::Identifier)
+                && (self.check_next(TokenType::Colon) || self.check_next(TokenType::Equal))
+            {
+                fields.push(self.field()?);
+                continue;
+            }
+            let kind = self.method_kind();
+            self.in_a_static = kind == MethodKind::Static;
+            let method = self.function("method");
+            self.in_a_static = false;
+            let method = method?;
+            Self::check_accessor(kind, &method)?;
+            methods.push((kind, method));
         }
 
         self.consume(
@@ -163,10 +464,226 @@ impl Parser {
         Ok(Rc::new(ClassStatement {
             name,
             methods,
+            fields,
             super_class,
+            traits,
+            constant,
+        }))
+    }
+
+    fn trait_declaration(&mut self) -> Result<Rc<dyn Statement>, (String, Token)> {
+        let name = self
+            .consume(TokenType::Identifier, String::from("Ocekavam nazev traitu."))?
+            .clone();
+        let constant = self.sealed && self.scopes.len() == 1;
+        self.declare(&name, constant);
+
+        self.consume(
+            TokenType::LeftBrace,
+            String::from("Ocekavam '{' pred zacatkem tela traitu."),
+        )?;
+        self.in_a_class = true;
+        let mut methods: Vec<Rc<dyn Statement>> = Vec::new();
+        let mut required: Vec<Function> = Vec::new();
+        while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+            match self.required_method()? {
+                Some(function) => required.push(function),
+                None => methods.push(self.function("method")?),
+            }
+        }
+        self.consume(
+            TokenType::RightBrace,
+            String::from("Ocekavam '}' po tele traitu."),
+        )?;
+        self.in_a_class = false;
+
+        Ok(Rc::new(TraitStatement {
+            name,
+            methods,
+            required,
+            constant,
+        }))
+    }
+
+    fn enum_declaration(&mut self) -> Result<Rc<dyn Statement>, (String, Token)> {
+        let name = self
+            .consume(TokenType::Identifier, String::from("Ocekavam nazev vyctu."))?
+            .clone();
+        let constant = self.sealed && self.scopes.len() == 1;
+        self.declare(&name, constant);
+
+        self.consume(
+            TokenType::LeftBrace,
+            String::from("Ocekavam '{' pred variantami vyctu."),
+        )?;
+        let mut variants: Vec<(Token, Vec<Token>)> = Vec::new();
+        while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+            let variant = self
+                .consume(TokenType::Identifier, String::from("Ocekavam jmeno varianty."))?
+                .clone();
+            if variants.iter().any(|(existing, _)| existing.lexeme == variant.lexeme) {
+                return Err((
+                    format!("Varianta '{}' uz ve vyctu je.", variant.lexeme),
+                    variant,
+                ));
+            }
+            let mut fields = Vec::new();
+            if self.matching(&[TokenType::LeftParen]) {
+                while !self.check(TokenType::RightParen) {
+                    fields.push(
+                        self.consume(TokenType::Identifier, String::from("Ocekavam jmeno hodnoty varianty."))?
+                            .clone(),
+                    );
+                    if !self.matching(&[TokenType::Comma]) {
+                        break;
+                    }
+                }
+                self.consume(
+                    TokenType::RightParen,
+                    String::from("Ocekavam ')' po hodnotach varianty."),
+                )?;
+            }
+            variants.push((variant, fields));
+            if !self.matching(&[TokenType::Comma]) {
+                break;
+            }
+        }
+        self.consume(
+            TokenType::RightBrace,
+            String::from("Ocekavam '}' po variantach vyctu."),
+        )?;
+
+        Ok(Rc::new(EnumStatement {
+            name,
+            variants,
+            constant,
+        }))
+    }
+
+    fn record_declaration(&mut self) -> Result<Rc<dyn Statement>, (String, Token)> {
+        let name = self
+            .consume(TokenType::Identifier, String::from("Ocekavam nazev zaznamu."))?
+            .clone();
+        let constant = self.sealed && self.scopes.len() == 1;
+        self.declare(&name, constant);
+
+        self.consume(
+            TokenType::LeftParen,
+            String::from("Ocekavam '(' po nazvu zaznamu."),
+        )?;
+        let parameters = self.parameters(false)?;
+        if let Some(rest) = parameters.iter().find(|parameter| parameter.rest) {
+            return Err((
+                String::from("Zaznam nemuze mit zbytkovy parametr."),
+                rest.name.clone(),
+            ));
+        }
+        for (i, parameter) in parameters.iter().enumerate() {
+            if parameters[..i].iter().any(|other| other.name.lexeme == parameter.name.lexeme) {
+                return Err((
+                    format!("Pole '{}' uz je v zaznamu deklarovane.", parameter.name.lexeme),
+                    parameter.name.clone(),
+                ));
+            }
+        }
+        self.consume(
+            TokenType::SemiColon,
+            String::from("Ocekavam ';' po deklaraci zaznamu."),
+        )?;
+
+        Ok(Rc::new(RecordStatement {
+            name,
+            parameters,
+            constant,
+        }))
+    }
+
+    /// Povinna metoda traitu je jen hlavicka zakoncena `;`. Pokud za
+    /// hlavickou nasleduje telo, parser se vrati a metodu nacte `function`.
+    fn required_method(&mut self) -> Result<Option<Function>, (String, Token)> {
+        let start = self.current;
+        let name = self
+            .consume(TokenType::Identifier, String::from("Ocekavam method jmeno."))?
+            .clone();
+        self.consume(
+            TokenType::LeftParen,
+            String::from("Ocekavam '(' po method jmenu."),
+        )?;
+        let parameters = self.parameters(false)?;
+        let return_type = self.return_type()?;
+        if !self.matching(&[TokenType::SemiColon]) {
+            self.current = start;
+            return Ok(None);
+        }
+        Ok(Some(Function {
+            name,
+            parameters,
+            body: Vec::new(),
+            generator: false,
+            constant: false,
+            return_type,
         }))
     }
 
+    /// Pole tridy: `jmeno: Typ;`, `jmeno = hodnota;` nebo oboji.
+    fn field(&mut self) -> Result<Field, (String, Token)> {
+        let name = self.advance().clone();
+        let annotation = if self.matching(&[TokenType::Colon]) {
+            Some(self.type_annotation()?)
+        } else {
+            None
+        };
+        let init = if self.matching(&[TokenType::Equal]) {
+            Some(self.expression()?)
+        } else {
+            None
+        };
+        self.consume(
+            TokenType::SemiColon,
+            String::from("Ocekavam ';' po deklaraci pole."),
+        )?;
+        Ok(Field {
+            name,
+            annotation,
+            init,
+        })
+    }
+
+    /// `static`, `get` a `set` jsou klicova slova jen pred jmenem metody,
+    /// jinak jde o obycejne identifikatory (napr. metoda `get()`).
+    fn method_kind(&mut self) -> MethodKind {
+        if !self.check(TokenType::Identifier) || !self.check_next(TokenType::Identifier) {
+            return MethodKind::Instance;
+        }
+        let kind = match &*self.peek().lexeme {
+            "static" => MethodKind::Static,
+            "get" => MethodKind::Getter,
+            "set" => MethodKind::Setter,
+            _ => return MethodKind::Instance,
+        };
+        self.advance();
+        kind
+    }
+
+    fn check_accessor(kind: MethodKind, method: &Rc<dyn Statement>) -> Result<(), (String, Token)> {
+        let function = match method.kind() {
+            StatementKind::Function(function) => function,
+            _ => return Ok(()),
+        };
+        let parameters = function.parameters.len();
+        match kind {
+            MethodKind::Getter if parameters != 0 => Err((
+                String::from("Getter nemuze mit parametry."),
+                function.name,
+            )),
+            MethodKind::Setter if parameters != 1 => Err((
+                String::from("Setter musi mit prave jeden parametr."),
+                function.name,
+            )),
+            _ => Ok(()),
+        }
+    }
+
     fn statement(&mut self) -> Result<Rc<dyn Statement>, (String, Token)> {
         if self.matching(&[TokenType::For]) {
             return self.for_statement();
@@ -183,8 +700,36 @@ impl Parser {
         if self.matching(&[TokenType::While]) {
             return self.while_statement();
         }
+        if self.matching(&[TokenType::Yield]) {
+            return self.yield_statement();
+        }
+        if self.matching(&[TokenType::Match]) {
+            let (keyword, value) = self.match_subject()?;
+            let arms = self.match_arms(|parser| {
+                // Prazdna vetev `_ => {}` je bezny zpusob, jak nic nedelat.
+                if parser.check(TokenType::LeftBrace) && parser.check_next(TokenType::RightBrace) {
+                    parser.advance();
+                    parser.advance();
+                    parser.matching(&[TokenType::Comma]);
+                    let body: Rc<dyn Statement> = Rc::new(Block { statements: Vec::new() });
+                    return Ok(body);
+                }
+                let body = parser.statement()?;
+                parser.matching(&[TokenType::Comma]);
+                Ok(body)
+            })?;
+            return Ok(Rc::new(MatchStatement {
+                keyword,
+                value,
+                arms: Rc::new(arms),
+            }));
+        }
         if self.matching(&[TokenType::LeftBrace]) {
+            let brace = self.previous().clone();
             let statements = self.block()?;
+            if statements.is_empty() {
+                self.warn(Lint::EmptyBlock, String::from("Prazdny blok."), &brace);
+            }
             return Ok(Rc::new(Block { statements }));
         }
         self.expression_statement()
@@ -197,6 +742,7 @@ impl Parser {
             TokenType::RightParen,
             String::from("Ocekavam ')' na konci podminky."),
         )?;
+        self.check_condition(&condition);
 
         let then_branch = self.statement()?;
         let mut else_branch = None;
@@ -209,11 +755,16 @@ impl Parser {
     }
     
     fn for_statement(&mut self) -> Result<Rc<dyn Statement>, (String, Token)> {
+        let keyword = self.previous().clone();
         self.consume(TokenType::LeftParen, String::from("Ocekavam '(' po 'for'."))?;
+        if self.is_for_in() {
+            return self.for_in_statement(keyword);
+        }
+        self.begin_scope();
         let init: Option<Rc<dyn Statement>> = if self.matching(&[TokenType::SemiColon]) {
             None
         } else if self.matching(&[TokenType::Var]) {
-            Some(self.var_declaration()?)
+            Some(self.var_declaration(false)?)
         } else {
             Some(self.expression_statement()?)
         };
@@ -224,6 +775,9 @@ impl Parser {
             None
         };
         self.consume(TokenType::SemiColon, String::from("Ocekavam ';' po podmince smycky."))?;
+        if let Some(condition) = &condition {
+            self.check_condition(condition);
+        }
         
         let increment: Option<Rc<dyn Expr>> = if !self.check(TokenType::RightParen) {
             Some(self.expression()?)
@@ -264,10 +818,54 @@ impl Parser {
                 })
             }
         }
+        self.end_scope();
         
         Ok(body)
     }
     
+    fn is_for_in(&self) -> bool {
+        let start = if self.check(TokenType::Var) {
+            self.current + 1
+        } else {
+            self.current
+        };
+        match (self.tokens.get(start), self.tokens.get(start + 1)) {
+            (Some(name), Some(next)) => {
+                name.token_type == TokenType::Identifier
+                    && (next.token_type == TokenType::In || next.token_type == TokenType::Comma)
+            }
+            _ => false,
+        }
+    }
+
+    fn for_in_statement(&mut self, keyword: Token) -> Result<Rc<dyn Statement>, (String, Token)> {
+        self.matching(&[TokenType::Var]);
+        let mut names = vec![self
+            .consume(TokenType::Identifier, String::from("Ocekavam jmeno promenne."))?
+            .clone()];
+        while self.matching(&[TokenType::Comma]) {
+            names.push(
+                self.consume(TokenType::Identifier, String::from("Ocekavam jmeno promenne."))?
+                    .clone(),
+            );
+        }
+        self.consume(TokenType::In, String::from("Ocekavam 'in' po promennych smycky."))?;
+        let iterable = self.expression()?;
+        self.consume(TokenType::RightParen, String::from("Ocekavam ')' po 'for'."))?;
+        self.begin_scope();
+        for name in &names {
+            self.declare(name, false);
+        }
+        let body = self.statement()?;
+        self.end_scope();
+        Ok(Rc::new(ForIn {
+            keyword,
+            names,
+            iterable,
+            body,
+        }))
+    }
+
     fn print_statement(&mut self) -> Result<Rc<dyn Statement>, (String, Token)> {
         let expr = self.expression()?;
         let consumed = self.consume(TokenType::SemiColon, String::from("Ocekevam ';' for vyrazu."));
@@ -294,23 +892,96 @@ impl Parser {
             TokenType::SemiColon,
             String::from("Ocekavam ';' po hodnote co mam vratit."),
         )?;
-        Ok(Rc::new(ReturnStatement { value }))
+        Ok(Rc::new(ReturnStatement { keyword, value }))
     }
     
-    fn var_declaration(&mut self) -> Result<Rc<dyn Statement>, (String, Token)> {
+    fn yield_statement(&mut self) -> Result<Rc<dyn Statement>, (String, Token)> {
+        let keyword = self.previous().clone();
+        if !self.in_a_function {
+            return Err((
+                String::from("'yield' lze pouzit jen uvnitr funkce."),
+                keyword,
+            ));
+        }
+        if self.in_an_init {
+            return Err((
+                String::from("Initializer nemuze byt generator."),
+                keyword,
+            ));
+        }
+        self.in_a_generator = true;
+        let value = self.expression()?;
+        self.consume(
+            TokenType::SemiColon,
+            String::from("Ocekavam ';' po hodnote pro 'yield'."),
+        )?;
+        Ok(Rc::new(Yield { keyword, value }))
+    }
+
+    fn var_declaration(&mut self, constant: bool) -> Result<Rc<dyn Statement>, (String, Token)> {
+        if self.check(TokenType::LeftBracket)
+            || self.check(TokenType::LeftParen)
+            || self.check(TokenType::LeftBrace)
+            || (self.check(TokenType::Identifier) && self.check_next(TokenType::Dot))
+        {
+            let token = self.peek().clone();
+            let pattern = self.pattern()?;
+            Self::check_destructuring(&pattern, &token)?;
+            self.consume(
+                TokenType::Equal,
+                String::from("Ocekavam '=' po vzoru promennych."),
+            )?;
+            let names = pattern.bindings();
+            for name in &names {
+                self.declare_pending(name, constant);
+            }
+            let init = self.expression()?;
+            self.consume(
+                TokenType::SemiColon,
+                String::from("Ocekavam ';' po deklaraci promenne."),
+            )?;
+            for name in &names {
+                self.define(name);
+            }
+            return Ok(Rc::new(DestructuringVar {
+                pattern,
+                token,
+                init,
+                constant,
+            }));
+        }
         let name = self
             .consume(TokenType::Identifier, String::from("Ocekavam jmeno promenne."))?
             .clone();
+        self.declare_pending(&name, constant);
+        let annotation = if self.matching(&[TokenType::Colon]) {
+            Some(self.type_annotation()?)
+        } else {
+            None
+        };
         let to_return: Result<Rc<dyn Statement>, (String, Token)> = if self.matching(&[TokenType::Equal])
         {
             let initializer = self.expression()?;
-            Ok(Rc::new(Var { name, init: initializer }))
+            Ok(Rc::new(Var {
+                name: name.clone(),
+                init: initializer,
+                constant,
+                annotation,
+            }))
+        } else if constant {
+            Err((
+                String::from("Konstanta musi mit pocatecni hodnotu."),
+                name.clone(),
+            ))
         } else {
             Ok(Rc::new(Var {
-                name,
+                name: name.clone(),
                 init: Rc::new(NoOp {}),
+                constant,
+                annotation,
             }))
         };
+        self.define(&name);
         self.consume(
             TokenType::SemiColon,
             String::from("Ocekavam ';' po deklaraci promenne."),
@@ -318,6 +989,28 @@ impl Parser {
         to_return
     }
     
+    /// Varuje pred `if (a = b)`, kde nejspis melo byt `==`.
+    /// Zamerne prirazeni jde zabalit do dalsich zavorek.
+    fn check_condition(&mut self, condition: &Rc<dyn Expr>) {
+        let assignment = matches!(
+            condition.kind(),
+            Kind::Assign
+                | Kind::CompoundAssign
+                | Kind::Set
+                | Kind::CompoundSet
+                | Kind::SetIndex
+                | Kind::DestructuringAssign
+        );
+        if assignment {
+            let token = self.previous().clone();
+            self.warn(
+                Lint::AssignInCondition,
+                String::from("Prirazeni v podmince, nemelo tu byt '=='?"),
+                &token,
+            );
+        }
+    }
+
     fn while_statement(&mut self) -> Result<Rc<dyn Statement>, (String, Token)> {
         self.consume(
             TokenType::LeftParen,
@@ -328,6 +1021,7 @@ impl Parser {
             TokenType::RightParen,
             String::from("Ocekavam ')' po podmince."),
         )?;
+        self.check_condition(&condition);
         let body = self.statement()?;
         Ok(Rc::new(While { condition, body }))
     }
@@ -352,84 +1046,374 @@ impl Parser {
 		if kind == "method" && name.lexeme == "init" {
 			self.in_an_init = true
 		}
+		let constant = kind == "function" && self.sealed && self.scopes.len() == 1;
+		if kind == "function" {
+			self.declare(&name, constant);
+		}
 
 		self.consume(
 			TokenType::LeftParen,
 			format!("Ocekavam '(' po {} jmenu.", kind),
 		)?;
-		let mut params: Vec<Token> = Vec::new();
-		if !self.check(TokenType::RightParen) {
-			params.push(
-				self.consume(
-					TokenType::Identifier,
-					String::from("Ocekavam jmeno parametru."),
-				)?
-				.clone(),
-			);
-			while self.matching(&[TokenType::Comma]) {
-				params.push(
-					self.consume(
-						TokenType::Identifier,
-						String::from("Ocekavam jmeno parametru."),
-					)?
-					.clone(),
-				);
-			}
-		}
-		self.consume(
-			TokenType::RightParen,
-			String::from("Ocekavam ')' po parametrech."),
-		)?;
-		self.consume(
-			TokenType::LeftBrace,
-			format!("Ocekavam '{{' pred {} telem funkce.", kind),
-		)?;
-		let body = self.block()?;
+		let in_a_lambda = self.in_a_lambda;
+		self.in_a_lambda = false;
+		let parts = self.function_parts(|parser| {
+			parser.consume(
+				TokenType::LeftBrace,
+				format!("Ocekavam '{{' pred {} telem funkce.", kind),
+			)?;
+			parser.function_body()
+		});
+		self.in_a_lambda = in_a_lambda;
+		let FunctionParts {
+			parameters: params,
+			return_type,
+			body,
+			generator,
+		} = parts?;
 		self.in_an_init = false;
 		Ok(Rc::new(Function {
 			name,
 			parameters: params.clone(),
 			body,
+			generator,
+			constant,
+			return_type,
 		}))
 	}
 
-	fn block(&mut self) -> Result<Vec<Rc<dyn Statement>>, (String, Token)> {
-		let mut statements: Vec<Rc<dyn Statement>> = Vec::new();
+	/// Parametry, navratovy typ a telo funkce nebo lambdy. Parametry se
+	/// deklaruji hned po nacteni, takze vychozi hodnota muze pouzit
+	/// parametry pred sebou, stejne jako pri volani v `bind_arguments`.
+	fn function_parts(
+		&mut self,
+		body: impl FnOnce(&mut Parser) -> Result<Vec<Rc<dyn Statement>>, (String, Token)>,
+	) -> Result<FunctionParts, (String, Token)> {
+		let saved = self.enter_function();
+		let parts = self.parameters(true).and_then(|parameters| {
+			let return_type = self.return_type()?;
+			Ok((parameters, return_type, body(self)?))
+		});
+		let generator = self.exit_function(saved);
+		let (parameters, return_type, body) = parts?;
+		Ok(FunctionParts {
+			parameters,
+			return_type,
+			body,
+			generator,
+		})
+	}
 
-		while !self.check(TokenType::RightBrace) && !self.is_at_end() {
-			statements.push(self.declaration()?)
-		}
+	/// Ulozi stav parseru pred funkci a otevre blok pro jeji parametry.
+	/// Vraci puvodni hodnoty, ktere se po telu obnovi v `exit_function`.
+	fn enter_function(&mut self) -> (bool, bool, usize) {
+		let saved = (self.in_a_function, self.in_a_generator, self.function_scope);
+		self.in_a_function = true;
+		self.in_a_generator = false;
+		self.function_scope = self.scopes.len();
+		self.begin_scope();
+		saved
+	}
 
+	/// Obnovi stav parseru po telu funkce a vrati, jestli v nem byl `yield`,
+	/// tedy jestli jde o generator.
+	fn exit_function(&mut self, saved: (bool, bool, usize)) -> bool {
+		let generator = self.in_a_generator;
+		(self.in_a_function, self.in_a_generator, self.function_scope) = saved;
+		self.end_scope();
+		generator
+	}
+
+	/// Seznam parametru az po `)`. S `declare` se kazdy hned deklaruje
+	/// v aktualnim bloku, to chteji funkce, ne zaznamy.
+	fn parameters(&mut self, declare: bool) -> Result<Vec<Parameter>, (String, Token)> {
+		let mut params: Vec<Parameter> = Vec::new();
+		if !self.check(TokenType::RightParen) {
+			params.push(self.parameter(&params, declare)?);
+			while self.matching(&[TokenType::Comma]) {
+				params.push(self.parameter(&params, declare)?);
+			}
+		}
 		self.consume(
-			TokenType::RightBrace,
-			String::from("Expect '}' after block."),
+			TokenType::RightParen,
+			String::from("Ocekavam ')' po parametrech."),
 		)?;
-		Ok(statements)
+		Ok(params)
 	}
 
-	fn assignment(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
-		let expr = self.or()?;
-		if self.matching(&[TokenType::Equal]) {
-			let equals = self.previous().clone();
-			let value = self.assignment()?;
-
+	fn parameter(&mut self, previous: &[Parameter], declare: bool) -> Result<Parameter, (String, Token)> {
+		let rest = self.matching(&[TokenType::DotDotDot]);
+		let name = self
+			.consume(
+				TokenType::Identifier,
+				String::from("Ocekavam jmeno parametru."),
+			)?
+			.clone();
+		if previous.iter().any(|parameter| parameter.rest) {
+			return Err((
+				String::from("Zbytkovy parametr musi byt posledni."),
+				name,
+			));
+		}
+		let annotation = if self.matching(&[TokenType::Colon]) {
+			Some(self.type_annotation()?)
+		} else {
+			None
+		};
+		// Vlastni vychozi hodnota parametr jeste cist nesmi.
+		if declare {
+			self.declare_parameter(&name);
+		}
+		let default = if !rest && self.matching(&[TokenType::Equal]) {
+			Some(self.assignment()?)
+		} else {
+			None
+		};
+		if declare {
+			self.define(&name);
+		}
+		if !rest && default.is_none() && previous.iter().any(|parameter| parameter.default.is_some()) {
+			return Err((
+				String::from("Parametr bez vychozi hodnoty nemuze nasledovat po parametru s vychozi hodnotou."),
+				name,
+			));
+		}
+		Ok(Parameter {
+			name,
+			default,
+			rest,
+			annotation,
+		})
+	}
+
+	fn return_type(&mut self) -> Result<Option<Annotation>, (String, Token)> {
+		if self.matching(&[TokenType::Arrow]) {
+			Ok(Some(self.type_annotation()?))
+		} else {
+			Ok(None)
+		}
+	}
+
+	/// Nazev typu, za kterym muze byt `?` pro hodnotu, ktera smi byt `nil`.
+	fn type_annotation(&mut self) -> Result<Annotation, (String, Token)> {
+		let token = self
+			.consume(TokenType::Identifier, String::from("Ocekavam nazev typu."))?
+			.clone();
+		let mut ty = Type::from_name(&token.lexeme);
+		if self.matching(&[TokenType::Question]) && ty != Type::Nil {
+			ty = Type::Optional(Box::new(ty));
+		}
+		Ok(Annotation { token, ty })
+	}
+
+	fn lambda(&mut self, keyword: Token, arrow: bool) -> Result<Rc<dyn Expr>, (String, Token)> {
+		let in_an_init = self.in_an_init;
+		self.in_an_init = false;
+		let in_a_lambda = self.in_a_lambda;
+		self.in_a_lambda = true;
+		let parts = self.function_parts(|parser| parser.lambda_body(arrow));
+		self.in_a_lambda = in_a_lambda;
+		let FunctionParts {
+			parameters,
+			return_type,
+			body,
+			generator,
+		} = parts?;
+		self.in_an_init = in_an_init;
+		Ok(Rc::new(Lambda {
+			keyword,
+			parameters,
+			body,
+			generator,
+			return_type,
+		}))
+	}
+
+	fn lambda_body(&mut self, arrow: bool) -> Result<Vec<Rc<dyn Statement>>, (String, Token)> {
+		if arrow {
+			let keyword = self
+				.consume(
+					TokenType::FatArrow,
+					String::from("Ocekavam '=>' po parametrech."),
+				)?
+				.clone();
+			if self.matching(&[TokenType::LeftBrace]) {
+				return self.function_body();
+			}
+			let value = self.assignment()?;
+			let statement: Rc<dyn Statement> = Rc::new(ReturnStatement { keyword, value });
+			return Ok(vec![statement]);
+		}
+		self.consume(
+			TokenType::LeftBrace,
+			String::from("Ocekavam '{' pred telem funkce."),
+		)?;
+		self.function_body()
+	}
+
+	fn is_arrow_lambda(&self) -> bool {
+		let mut depth = 0;
+		for (i, token) in self.tokens.iter().enumerate().skip(self.current) {
+			match token.token_type {
+				TokenType::LeftParen => depth += 1,
+				TokenType::RightParen => {
+					depth -= 1;
+					if depth == 0 {
+						return matches!(
+							self.tokens.get(i + 1).map(|t| &t.token_type),
+							Some(TokenType::FatArrow | TokenType::Arrow)
+						);
+					}
+				}
+				TokenType::SemiColon | TokenType::EOF => return false,
+				_ => {}
+			}
+		}
+		false
+	}
+
+	fn block(&mut self) -> Result<Vec<Rc<dyn Statement>>, (String, Token)> {
+		self.begin_scope();
+		let statements = self.statements()?;
+		self.end_scope();
+
+		self.consume(
+			TokenType::RightBrace,
+			String::from("Expect '}' after block."),
+		)?;
+		Ok(statements)
+	}
+
+	/// Telo funkce za `{`. Jeho promenne patri do stejneho bloku jako
+	/// parametry, takze `var a` v tele nemuze zastinit parametr `a`.
+	fn function_body(&mut self) -> Result<Vec<Rc<dyn Statement>>, (String, Token)> {
+		let statements = self.statements()?;
+		self.consume(
+			TokenType::RightBrace,
+			String::from("Expect '}' after block."),
+		)?;
+		Ok(statements)
+	}
+
+	/// Prikazy bloku az po `}`.
+	fn statements(&mut self) -> Result<Vec<Rc<dyn Statement>>, (String, Token)> {
+		let mut statements: Vec<Rc<dyn Statement>> = Vec::new();
+		let mut returned = false;
+		let mut reported = false;
+		while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+			if returned && !reported {
+				let token = self.peek().clone();
+				self.error(String::from("Nedosazitelny kod po 'return'."), &token);
+				reported = true;
+			}
+			let statement = self.declaration()?;
+			returned |= diverges(&statement);
+			statements.push(statement);
+		}
+		Ok(statements)
+	}
+
+	fn assignment(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
+		let expr = self.conditional()?;
+		if self.matching(&[TokenType::Equal]) {
+			let equals = self.previous().clone();
+			let value = self.assignment()?;
+
 			match expr.kind() {
-				Kind::Variable(name) => Ok(Rc::new(Assign { name, value })),
+				Kind::Variable(name) => {
+					self.check_assignable(&name);
+					Ok(Rc::new(Assign { name, value }))
+				}
 				Kind::Get(name, object) => Ok(Rc::new(Set {
 					object,
 					name,
 					value,
 				})),
+				Kind::Index(bracket, object, index) => Ok(Rc::new(SetIndex {
+					object,
+					bracket,
+					index,
+					op: None,
+					value,
+				})),
+				Kind::List(_) | Kind::Tuple(_) => {
+					let pattern = Self::assignment_pattern(&expr, &equals)?;
+					for name in pattern.bindings() {
+						self.check_assignable(&name);
+					}
+					Ok(Rc::new(DestructuringAssign {
+						pattern,
+						equals,
+						value,
+					}))
+				}
 				_ => {
 					let error: String = String::from("Neznamy typ promenne.");
 					Err((error, equals))
 				}
 			}
+		} else if self.matching(&[
+			TokenType::PlusEqual,
+			TokenType::MinusEqual,
+			TokenType::StarEqual,
+			TokenType::SlashEqual,
+			TokenType::PercentEqual,
+		]) {
+			let op = self.previous().clone();
+			let value = self.assignment()?;
+
+			match expr.kind() {
+				Kind::Variable(name) => {
+					self.check_assignable(&name);
+					Ok(Rc::new(CompoundAssign { name, op, value }))
+				}
+				Kind::Get(name, object) => Ok(Rc::new(CompoundSet {
+					object,
+					name,
+					op,
+					value,
+				})),
+				Kind::Index(bracket, object, index) => Ok(Rc::new(SetIndex {
+					object,
+					bracket,
+					index,
+					op: Some(op),
+					value,
+				})),
+				_ => Err((String::from("Neznamy typ promenne."), op)),
+			}
 		} else {
 			Ok(expr)
 		}
 	}
 
+	fn conditional(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
+		let condition = self.coalesce()?;
+		if self.matching(&[TokenType::Question]) {
+			let then_branch = self.expression()?;
+			self.consume(
+				TokenType::Colon,
+				String::from("Ocekavam ':' v podminenem vyrazu."),
+			)?;
+			let else_branch = self.conditional()?;
+			return Ok(Rc::new(Conditional {
+				condition,
+				then_branch,
+				else_branch,
+			}));
+		}
+		Ok(condition)
+	}
+
+	fn coalesce(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
+		let mut expr = self.or()?;
+		while self.matching(&[TokenType::QuestionQuestion]) {
+			let right = self.or()?;
+			expr = Rc::new(Coalesce { left: expr, right });
+		}
+		Ok(expr)
+	}
+
 	fn or(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
 		let mut expr = self.and()?;
 
@@ -465,6 +1449,16 @@ impl Parser {
 		while matching {
 			let op = self.previous().clone();
 			let right = self.comparison()?;
+			if let (Some(left_type), Some(right_type)) = (static_type(&expr), static_type(&right)) {
+				if left_type != right_type {
+					let result = op.token_type == TokenType::BangEqual;
+					self.warn(
+						Lint::TypeComparison,
+						format!("Porovnani typu {} a {} je vzdy {}.", left_type, right_type, result),
+						&op,
+					);
+				}
+			}
 			expr = Rc::new(Binary {
 				left: expr,
 				op,
@@ -476,13 +1470,150 @@ impl Parser {
 	}
 
 	fn comparison(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
-		let mut expr = self.term()?;
+		let mut expr = self.range()?;
 		let types = &[
 			TokenType::Greater,
 			TokenType::GreaterEqual,
 			TokenType::Less,
 			TokenType::LessEqual,
+			TokenType::In,
 		];
+		loop {
+			if self.matching(types) {
+				let op = self.previous().clone();
+				let right = self.range()?;
+				expr = Rc::new(Binary {
+					left: expr,
+					op,
+					right,
+				});
+			} else if self.matching(&[TokenType::Not]) {
+				let mut op = self.previous().clone();
+				self.consume(TokenType::In, String::from("Ocekavam 'in' po 'not'."))?;
+				op.token_type = TokenType::NotIn;
+				op.lexeme = String::from("not in");
+				let right = self.range()?;
+				expr = Rc::new(Binary {
+					left: expr,
+					op,
+					right,
+				});
+			} else if self.check(TokenType::Identifier) && self.peek().lexeme == "is" {
+				// `is` neni klicove slovo, za vyrazem ale identifikator stat nemuze.
+				let keyword = self.advance().clone();
+				let target = self.range()?;
+				expr = Rc::new(Is {
+					value: expr,
+					keyword,
+					target,
+				});
+			} else {
+				return Ok(expr);
+			}
+		}
+	}
+
+	fn range(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
+		let range_types = &[TokenType::DotDot, TokenType::DotDotEqual];
+		let start = if self.check(TokenType::DotDot) || self.check(TokenType::DotDotEqual) {
+			None
+		} else {
+			let start = self.bit_or()?;
+			if !self.check(TokenType::DotDot) && !self.check(TokenType::DotDotEqual) {
+				return Ok(start);
+			}
+			Some(start)
+		};
+		self.matching(range_types);
+		let op = self.previous().clone();
+		let end = if self.is_range_end() {
+			None
+		} else {
+			Some(self.bit_or()?)
+		};
+		let step = if self.check(TokenType::Identifier) && self.peek().lexeme == "step" {
+			self.advance();
+			Some(self.bit_or()?)
+		} else {
+			None
+		};
+		Ok(Rc::new(RangeExpr {
+			start,
+			end,
+			step,
+			inclusive: op.token_type == TokenType::DotDotEqual,
+			op,
+		}))
+	}
+
+	fn is_range_end(&self) -> bool {
+		match self.peek().token_type {
+			TokenType::RightBracket
+			| TokenType::RightParen
+			| TokenType::RightBrace
+			| TokenType::Comma
+			| TokenType::SemiColon
+			| TokenType::Colon
+			| TokenType::EOF => true,
+			TokenType::Identifier => self.peek().lexeme == "step",
+			_ => false,
+		}
+	}
+
+	fn bit_or(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
+		let mut expr = self.bit_xor()?;
+		let types = &[TokenType::Pipe];
+		let mut matching = self.matching(types);
+		while matching {
+			let op = self.previous().clone();
+			let right = self.bit_xor()?;
+			expr = Rc::new(Binary {
+				left: expr,
+				op,
+				right,
+			});
+			matching = self.matching(types);
+		}
+		Ok(expr)
+	}
+
+	fn bit_xor(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
+		let mut expr = self.bit_and()?;
+		let types = &[TokenType::Caret];
+		let mut matching = self.matching(types);
+		while matching {
+			let op = self.previous().clone();
+			let right = self.bit_and()?;
+			expr = Rc::new(Binary {
+				left: expr,
+				op,
+				right,
+			});
+			matching = self.matching(types);
+		}
+		Ok(expr)
+	}
+
+	fn bit_and(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
+		let mut expr = self.shift()?;
+		let types = &[TokenType::Ampersand];
+		let mut matching = self.matching(types);
+		while matching {
+			let op = self.previous().clone();
+			let right = self.shift()?;
+			expr = Rc::new(Binary {
+				left: expr,
+				op,
+				right,
+			});
+			matching = self.matching(types);
+		}
+		Ok(expr)
+	}
+
+	fn shift(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
+		let mut expr = self.term()?;
+		let types = &[TokenType::LessLess, TokenType::GreaterGreater];
 		let mut matching = self.matching(types);
 		while matching {
 			let op = self.previous().clone();
@@ -516,7 +1647,12 @@ impl Parser {
 
 	fn factor(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
 		let mut expr = self.unary()?;
-		let types = &[TokenType::Slash, TokenType::Star];
+		let types = &[
+			TokenType::Slash,
+			TokenType::Star,
+			TokenType::TildeSlash,
+			TokenType::Percent,
+		];
 		let mut matching = self.matching(types);
 		while matching {
 			let op = self.previous().clone();
@@ -532,7 +1668,12 @@ impl Parser {
 	}
 
 	fn unary(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
-		let types = &[TokenType::Minus, TokenType::Bang];
+		if self.matching(&[TokenType::PlusPlus, TokenType::MinusMinus]) {
+			let op = self.previous().clone();
+			let target = self.unary()?;
+			return self.increment(target, op, true);
+		}
+		let types = &[TokenType::Minus, TokenType::Bang, TokenType::Tilde];
 		let matching = self.matching(types);
 		if matching {
 			let op = self.previous().clone();
@@ -540,11 +1681,73 @@ impl Parser {
 			let expr = Rc::new(Unary { operator: op, right });
 			return Ok(expr);
 		}
-		self.call()
+		self.power()
+	}
+
+	fn increment(
+		&mut self,
+		target: Rc<dyn Expr>,
+		op: Token,
+		prefix: bool,
+	) -> Result<Rc<dyn Expr>, (String, Token)> {
+		match target.kind() {
+			Kind::Variable(name) => {
+				self.check_assignable(&name);
+				Ok(Rc::new(Increment {
+					name,
+					object: None,
+					index: None,
+					op,
+					prefix,
+				}))
+			}
+			Kind::Get(name, object) => Ok(Rc::new(Increment {
+				name,
+				object: Some(object),
+				index: None,
+				op,
+				prefix,
+			})),
+			Kind::Index(bracket, object, index) => Ok(Rc::new(Increment {
+				name: bracket,
+				object: Some(object),
+				index: Some(index),
+				op,
+				prefix,
+			})),
+			_ => Err((String::from("Neznamy typ promenne."), op)),
+		}
+	}
+
+	fn power(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
+		let expr = self.postfix()?;
+		if self.matching(&[TokenType::StarStar]) {
+			let op = self.previous().clone();
+			let right = self.unary()?;
+			return Ok(Rc::new(Binary {
+				left: expr,
+				op,
+				right,
+			}));
+		}
+		Ok(expr)
+	}
+
+	fn postfix(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
+		let expr = self.call()?;
+		if self.matching(&[TokenType::PlusPlus, TokenType::MinusMinus]) {
+			let op = self.previous().clone();
+			return self.increment(expr, op, false);
+		}
+		Ok(expr)
 	}
 
 	fn call(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
-		let mut expr = self.primary()?;
+		let expr = self.primary()?;
+		self.call_chain(expr)
+	}
+
+	fn call_chain(&mut self, mut expr: Rc<dyn Expr>) -> Result<Rc<dyn Expr>, (String, Token)> {
 		loop {
 			if self.matching(&[TokenType::LeftParen]) {
 				expr = self.finish_call(expr)?;
@@ -559,6 +1762,33 @@ impl Parser {
 					name,
 					object: Rc::clone(&expr),
 				})
+			} else if self.matching(&[TokenType::LeftBracket]) {
+				let bracket = self.previous().clone();
+				let index = self.expression()?;
+				self.consume(
+					TokenType::RightBracket,
+					String::from("Ocekavam ']' po indexu."),
+				)?;
+				expr = Rc::new(Index {
+					object: expr,
+					bracket,
+					index,
+				});
+			} else if self.matching(&[TokenType::QuestionDot]) {
+				let mut receiver = self.previous().clone();
+				receiver.lexeme = String::from(CHAIN_RECEIVER);
+				let name = self
+					.consume(
+						TokenType::Identifier,
+						String::from("Ocekavam jmeno property po '?.'."),
+					)?
+					.clone();
+				let link = Rc::new(Get {
+					name,
+					object: Rc::new(Variable { name: receiver }),
+				});
+				let chain = self.call_chain(link)?;
+				return Ok(Rc::new(OptionalChain { object: expr, chain }));
 			} else {
 				break;
 			}
@@ -568,10 +1798,11 @@ impl Parser {
 
 	fn finish_call(&mut self, caller: Rc<dyn Expr>) -> Result<Rc<dyn Expr>, (String, Token)> {
 		let mut arguments: Vec<Rc<dyn Expr>> = Vec::new();
+		let mut named: Vec<(Token, Rc<dyn Expr>)> = Vec::new();
 		if !self.check(TokenType::RightParen) {
-			arguments.push(self.expression()?);
+			self.argument(&mut arguments, &mut named)?;
 			while self.matching(&[TokenType::Comma]) {
-				arguments.push(self.expression()?);
+				self.argument(&mut arguments, &mut named)?;
 			}
 		}
 
@@ -585,9 +1816,30 @@ impl Parser {
 			calling: caller,
 			parent: paren,
 			arguments,
+			named,
 		}))
 	}
 
+	fn argument(
+		&mut self,
+		arguments: &mut Vec<Rc<dyn Expr>>,
+		named: &mut Vec<(Token, Rc<dyn Expr>)>,
+	) -> Result<(), (String, Token)> {
+		if self.check(TokenType::Identifier) && self.check_next(TokenType::Colon) {
+			let name = self.advance().clone();
+			self.advance();
+			named.push((name, self.expression()?));
+		} else if !named.is_empty() {
+			return Err((
+				String::from("Pozicni argument nemuze nasledovat po pojmenovanem."),
+				self.peek().clone(),
+			));
+		} else {
+			arguments.push(self.expression()?);
+		}
+		Ok(())
+	}
+
 	fn primary(&mut self) -> Result<Rc<dyn Expr>, (String, Token)> {
 		if self.matching(&[TokenType::False]) {
 			return Ok(Rc::new(Literal {
@@ -602,9 +1854,9 @@ impl Parser {
 		}
 
 		if self.matching(&[TokenType::Identifier]) {
-			return Ok(Rc::new(Variable {
-				name: self.previous().clone(),
-			}));
+			let name = self.previous().clone();
+			self.resolve(&name);
+			return Ok(Rc::new(Variable { name }));
 		}
 
 		if self.matching(&[TokenType::True]) {
@@ -619,8 +1871,28 @@ impl Parser {
 			}));
 		}
 
+		if self.matching(&[TokenType::Fun]) {
+			let keyword = self.previous().clone();
+			self.consume(
+				TokenType::LeftParen,
+				String::from("Ocekavam '(' po 'fun'."),
+			)?;
+			return self.lambda(keyword, false);
+		}
+
+		if self.check(TokenType::LeftParen) && self.is_arrow_lambda() {
+			let keyword = self.advance().clone();
+			return self.lambda(keyword, true);
+		}
+
 		if self.matching(&[TokenType::LeftParen]) {
+			if self.matching(&[TokenType::RightParen]) {
+				return Ok(Rc::new(TupleLiteral { elements: Vec::new() }));
+			}
 			let expr = self.expression()?;
+			if self.matching(&[TokenType::Comma]) {
+				return self.tuple(expr);
+			}
 			self.consume(
 				TokenType::RightParen,
 				String::from("Ocekavam ')' po vyrazu."),	
@@ -628,11 +1900,73 @@ impl Parser {
 			return Ok(Rc::new(Grouping { expression: expr }));
 		}
 
+		if self.matching(&[TokenType::Match]) {
+			let (keyword, value) = self.match_subject()?;
+			let arms = self.match_arms(|parser| {
+				let body = parser.expression()?;
+				if !parser.check(TokenType::RightBrace) {
+					parser.consume(
+						TokenType::Comma,
+						String::from("Ocekavam ',' mezi vetvemi 'match'."),
+					)?;
+				}
+				Ok(body)
+			})?;
+			return Ok(Rc::new(Match {
+				keyword,
+				value,
+				arms,
+			}));
+		}
+
+		if self.matching(&[TokenType::LeftBracket]) {
+			let mut elements: Vec<Rc<dyn Expr>> = Vec::new();
+			while !self.check(TokenType::RightBracket) {
+				elements.push(self.expression()?);
+				if !self.matching(&[TokenType::Comma]) {
+					break;
+				}
+			}
+			self.consume(
+				TokenType::RightBracket,
+				String::from("Ocekavam ']' po prvcich seznamu."),
+			)?;
+			return Ok(Rc::new(ListLiteral { elements }));
+		}
+
+		if self.matching(&[TokenType::HashLeftBrace]) {
+			let brace = self.previous().clone();
+			let mut elements: Vec<Rc<dyn Expr>> = Vec::new();
+			while !self.check(TokenType::RightBrace) {
+				elements.push(self.expression()?);
+				if !self.matching(&[TokenType::Comma]) {
+					break;
+				}
+			}
+			self.consume(
+				TokenType::RightBrace,
+				String::from("Ocekavam '}' po prvcich mnoziny."),
+			)?;
+			return Ok(Rc::new(SetLiteral { brace, elements }));
+		}
+
 		if self.matching(&[TokenType::This]) {
+			if self.in_a_static {
+				return Err((
+					String::from("Nelze pouzit 'this' ve staticke metode."),
+					self.previous().clone(),
+				));
+			}
 			return if self.in_a_class {
-				Ok(Rc::new(This {
-					keyword: self.previous().clone(),
-				}))
+				let keyword = self.previous().clone();
+				if self.in_a_lambda {
+					self.warn(
+						Lint::CapturedThis,
+						String::from("Lambda zachycuje 'this' z okolni metody."),
+						&keyword,
+					);
+				}
+				Ok(Rc::new(This { keyword }))
 			} else {
 				return Err((
 					String::from("Nelze pouzit 'this' mimo tridu."),
@@ -649,6 +1983,12 @@ impl Parser {
 					keyword,
 				));
 			}
+			if self.in_a_static {
+				return Err((
+					String::from("Nelze pouzit 'super' ve staticke metode."),
+					keyword,
+				));
+			}
 			if !self.in_a_subclass {
 				return Err((
 					String::from("Nelze pouzit 'super' we tride beze super tridy."),
@@ -667,4 +2007,298 @@ impl Parser {
 
 		Ok(Rc::new(NoOp {}))
 	}
+
+	/// Zbytek ntice `(a, b)` po prvnim prvku a carce. Ntice s jednim
+	/// prvkem se pise `(a,)`, aby se nepletla se zavorkami.
+	fn tuple(&mut self, first: Rc<dyn Expr>) -> Result<Rc<dyn Expr>, (String, Token)> {
+		let mut elements = vec![first];
+		while !self.check(TokenType::RightParen) {
+			elements.push(self.expression()?);
+			if !self.matching(&[TokenType::Comma]) {
+				break;
+			}
+		}
+		self.consume(
+			TokenType::RightParen,
+			String::from("Ocekavam ')' po prvcich ntice."),
+		)?;
+		Ok(Rc::new(TupleLiteral { elements }))
+	}
+
+	fn match_subject(&mut self) -> Result<(Token, Rc<dyn Expr>), (String, Token)> {
+		let keyword = self.previous().clone();
+		self.consume(
+			TokenType::LeftParen,
+			String::from("Ocekavam '(' po 'match'."),
+		)?;
+		let value = self.expression()?;
+		self.consume(
+			TokenType::RightParen,
+			String::from("Ocekavam ')' po hodnote pro 'match'."),
+		)?;
+		self.consume(
+			TokenType::LeftBrace,
+			String::from("Ocekavam '{' pred vetvemi 'match'."),
+		)?;
+		Ok((keyword, value))
+	}
+
+	fn match_arms<T>(
+		&mut self,
+		mut body: impl FnMut(&mut Self) -> Result<T, (String, Token)>,
+	) -> Result<Vec<MatchArm<T>>, (String, Token)> {
+		let mut arms = Vec::new();
+		while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+			let pattern = self.pattern()?;
+			self.begin_scope();
+			for name in pattern.bindings() {
+				self.declare(&name, false);
+			}
+			let guard = if self.matching(&[TokenType::If]) {
+				Some(self.expression()?)
+			} else {
+				None
+			};
+			self.consume(
+				TokenType::FatArrow,
+				String::from("Ocekavam '=>' po vzoru."),
+			)?;
+			let body = body(self)?;
+			self.end_scope();
+			arms.push(MatchArm {
+				pattern,
+				guard,
+				body,
+			});
+		}
+		self.consume(
+			TokenType::RightBrace,
+			String::from("Ocekavam '}' po vetvich 'match'."),
+		)?;
+		Ok(arms)
+	}
+
+	fn pattern(&mut self) -> Result<Pattern, (String, Token)> {
+		let first = self.single_pattern()?;
+		if !self.check(TokenType::Pipe) {
+			return Ok(first);
+		}
+		let mut alternatives = vec![first];
+		while self.matching(&[TokenType::Pipe]) {
+			alternatives.push(self.single_pattern()?);
+		}
+		Ok(Pattern::Alternatives(alternatives))
+	}
+
+	fn single_pattern(&mut self) -> Result<Pattern, (String, Token)> {
+		if self.matching(&[TokenType::Minus]) {
+			let minus = self.previous().clone();
+			let number = self
+				.consume(
+					TokenType::Number,
+					String::from("Ocekavam cislo po '-' ve vzoru."),
+				)?
+				.literal
+				.clone();
+			let value = binary_operation(TokenType::Minus, minus, Value::Int(0), number)?;
+			return Ok(Pattern::Literal(value));
+		}
+		if self.matching(&[TokenType::String, TokenType::Number]) {
+			return Ok(Pattern::Literal(self.previous().literal.clone()));
+		}
+		if self.matching(&[TokenType::True]) {
+			return Ok(Pattern::Literal(Value::Bool(true)));
+		}
+		if self.matching(&[TokenType::False]) {
+			return Ok(Pattern::Literal(Value::Bool(false)));
+		}
+		if self.matching(&[TokenType::Nil]) {
+			return Ok(Pattern::Literal(Value::None));
+		}
+		if self.matching(&[TokenType::LeftBracket]) {
+			let mut elements = Vec::new();
+			let mut rest = None;
+			while !self.check(TokenType::RightBracket) {
+				if self.matching(&[TokenType::DotDotDot]) {
+					rest = Some(
+						self.consume(
+							TokenType::Identifier,
+							String::from("Ocekavam jmeno po '...'."),
+						)?
+						.clone(),
+					);
+					break;
+				}
+				elements.push(self.pattern()?);
+				if !self.matching(&[TokenType::Comma]) {
+					break;
+				}
+			}
+			self.consume(
+				TokenType::RightBracket,
+				String::from("Ocekavam ']' po vzoru seznamu."),
+			)?;
+			return Ok(Pattern::List(elements, rest));
+		}
+		if self.matching(&[TokenType::LeftParen]) {
+			let first = self.pattern()?;
+			if !self.matching(&[TokenType::Comma]) {
+				self.consume(
+					TokenType::RightParen,
+					String::from("Ocekavam ')' po vzoru."),
+				)?;
+				return Ok(first);
+			}
+			let mut elements = vec![first];
+			while !self.check(TokenType::RightParen) {
+				elements.push(self.pattern()?);
+				if !self.matching(&[TokenType::Comma]) {
+					break;
+				}
+			}
+			self.consume(
+				TokenType::RightParen,
+				String::from("Ocekavam ')' po vzoru ntice."),
+			)?;
+			return Ok(Pattern::Tuple(elements));
+		}
+		if self.matching(&[TokenType::Identifier]) {
+			let name = self.previous().clone();
+			if name.lexeme == "_" {
+				return Ok(Pattern::Wildcard);
+			}
+			if self.matching(&[TokenType::Dot]) {
+				return self.variant_pattern(name);
+			}
+			if !self.matching(&[TokenType::LeftBrace]) {
+				return Ok(Pattern::Binding(name));
+			}
+			self.resolve(&name);
+			return Ok(Pattern::Instance(Some(name), self.field_patterns()?));
+		}
+		if self.matching(&[TokenType::LeftBrace]) {
+			return Ok(Pattern::Instance(None, self.field_patterns()?));
+		}
+		Err((String::from("Ocekavam vzor."), self.peek().clone()))
+	}
+
+	/// `Vycet.Varianta` nebo `Vycet.Varianta(vzor, ...)`.
+	fn variant_pattern(&mut self, owner: Token) -> Result<Pattern, (String, Token)> {
+		self.resolve(&owner);
+		let name = self
+			.consume(TokenType::Identifier, String::from("Ocekavam jmeno varianty."))?
+			.clone();
+		if !self.matching(&[TokenType::LeftParen]) {
+			return Ok(Pattern::Variant(owner, name, None));
+		}
+		let mut elements = Vec::new();
+		while !self.check(TokenType::RightParen) {
+			elements.push(self.pattern()?);
+			if !self.matching(&[TokenType::Comma]) {
+				break;
+			}
+		}
+		self.consume(
+			TokenType::RightParen,
+			String::from("Ocekavam ')' po vzoru varianty."),
+		)?;
+		Ok(Pattern::Variant(owner, name, Some(elements)))
+	}
+
+	fn field_patterns(&mut self) -> Result<Vec<(Token, Pattern)>, (String, Token)> {
+		let mut fields = Vec::new();
+		while !self.check(TokenType::RightBrace) {
+			let field = self
+				.consume(
+					TokenType::Identifier,
+					String::from("Ocekavam jmeno vlastnosti ve vzoru."),
+				)?
+				.clone();
+			let pattern = if self.matching(&[TokenType::Colon]) {
+				self.pattern()?
+			} else {
+				Pattern::Binding(field.clone())
+			};
+			fields.push((field, pattern));
+			if !self.matching(&[TokenType::Comma]) {
+				break;
+			}
+		}
+		self.consume(
+			TokenType::RightBrace,
+			String::from("Ocekavam '}' po vzoru instance."),
+		)?;
+		Ok(fields)
+	}
+
+	/// Overi, ze vzor jde pouzit na leve strane `var` nebo prirazeni,
+	/// tedy ze obsahuje jen jmena, `_`, seznamy a vlastnosti.
+	fn check_destructuring(pattern: &Pattern, token: &Token) -> Result<(), (String, Token)> {
+		match pattern {
+			Pattern::Wildcard | Pattern::Binding(_) => Ok(()),
+			Pattern::List(elements, _) | Pattern::Tuple(elements) => elements
+				.iter()
+				.try_for_each(|element| Self::check_destructuring(element, token)),
+			Pattern::Instance(None, fields) => fields
+				.iter()
+				.try_for_each(|(_, field)| Self::check_destructuring(field, token)),
+			Pattern::Variant(_, _, elements) => elements
+				.iter()
+				.flatten()
+				.try_for_each(|element| Self::check_destructuring(element, token)),
+			_ => Err((
+				String::from("Pri rozkladu lze pouzit jen jmena, seznamy, ntice a vlastnosti."),
+				token.clone(),
+			)),
+		}
+	}
+
+	/// Prevede levou stranu prirazeni `[a, b] = ...` na vzor.
+	fn assignment_pattern(expr: &Rc<dyn Expr>, equals: &Token) -> Result<Pattern, (String, Token)> {
+		match expr.kind() {
+			Kind::Variable(name) if name.lexeme == "_" => Ok(Pattern::Wildcard),
+			Kind::Variable(name) => Ok(Pattern::Binding(name)),
+			Kind::List(elements) => Ok(Pattern::List(
+				elements
+					.iter()
+					.map(|element| Self::assignment_pattern(element, equals))
+					.collect::<Result<Vec<Pattern>, (String, Token)>>()?,
+				None,
+			)),
+			Kind::Tuple(elements) => Ok(Pattern::Tuple(
+				elements
+					.iter()
+					.map(|element| Self::assignment_pattern(element, equals))
+					.collect::<Result<Vec<Pattern>, (String, Token)>>()?,
+			)),
+			_ => Err((String::from("Neznamy typ promenne."), equals.clone())),
+		}
+	}
+}
+
+/// Jestli se za prikazem uz nikdy nepokracuje: `return`, blok, ktery
+/// konci `return`, nebo `if`, ve kterem se vraci v obou vetvich.
+fn diverges(statement: &Rc<dyn Statement>) -> bool {
+	match statement.kind() {
+		StatementKind::ReturnStatement => true,
+		StatementKind::Block(statements) => statements.last().is_some_and(diverges),
+		StatementKind::If(_, then, Some(otherwise)) => diverges(&then) && diverges(&otherwise),
+		_ => false,
+	}
+}
+
+/// Typ vyrazu, pokud je znamy uz pri parsovani. `nil` se do porovnani
+/// typu nepocita, protoze `x == nil` je bezna kontrola.
+fn static_type(expr: &Rc<dyn Expr>) -> Option<&'static str> {
+	match expr.kind() {
+		Kind::Literal(Value::Int(_) | Value::Number(_) | Value::BigInt(_)) => Some("cislo"),
+		Kind::Literal(Value::String(_)) => Some("retezec"),
+		Kind::Literal(Value::Bool(_)) => Some("bool"),
+		Kind::List(_) => Some("seznam"),
+		Kind::Tuple(_) => Some("ntice"),
+		Kind::SetLiteral => Some("mnozina"),
+		Kind::Lambda => Some("funkce"),
+		Kind::Range => Some("rozsah"),
+		_ => None,
+	}
 }
@@ -0,0 +1,379 @@
+use crate::enums::VariantValue;
+use crate::expressions::{is_truth, Expr};
+use crate::moonenv::Environment;
+use crate::token::Token;
+use crate::value::{Class, Trait, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub enum Pattern {
+    Wildcard,
+    Literal(Value),
+    Binding(Token),
+    List(Vec<Pattern>, Option<Token>),
+    Tuple(Vec<Pattern>),
+    Instance(Option<Token>, Vec<(Token, Pattern)>),
+    Variant(Token, Token, Option<Vec<Pattern>>),
+    Alternatives(Vec<Pattern>),
+}
+
+pub struct MatchArm<T> {
+    pub(crate) pattern: Pattern,
+    pub(crate) guard: Option<Rc<dyn Expr>>,
+    pub(crate) body: T,
+}
+
+impl Pattern {
+    /// Zkusi na hodnotu napasovat vzor. Promenne, ktere vzor zachyti,
+    /// pridava do `bindings`; pri neuspechu tam muzou zustat jen castecne.
+    pub fn matches(
+        &self,
+        value: &Value,
+        env: &Rc<Environment>,
+        bindings: &mut Vec<(String, Value)>,
+    ) -> Result<bool, (String, Token)> {
+        match self {
+            Pattern::Wildcard => Ok(true),
+            Pattern::Literal(literal) => Ok(literal == value),
+            Pattern::Binding(name) => {
+                bindings.push((name.lexeme.clone(), value.clone()));
+                Ok(true)
+            }
+            Pattern::List(elements, rest) => {
+                let items = match value {
+                    Value::List(a) => a.borrow().clone(),
+                    _ => return Ok(false),
+                };
+                let length_matches = match rest {
+                    None => items.len() == elements.len(),
+                    Some(_) => items.len() >= elements.len(),
+                };
+                if !length_matches {
+                    return Ok(false);
+                }
+                for (pattern, item) in elements.iter().zip(&items) {
+                    if !pattern.matches(item, env, bindings)? {
+                        return Ok(false);
+                    }
+                }
+                if let Some(rest) = rest {
+                    let remaining = items[elements.len()..].to_vec();
+                    bindings.push((rest.lexeme.clone(), Value::List(Rc::new(RefCell::new(remaining)))));
+                }
+                Ok(true)
+            }
+            Pattern::Tuple(elements) => {
+                let items = match value {
+                    Value::Tuple(items) if items.len() == elements.len() => items,
+                    _ => return Ok(false),
+                };
+                for (pattern, item) in elements.iter().zip(items.iter()) {
+                    if !pattern.matches(item, env, bindings)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Pattern::Instance(class_name, fields) => {
+                if !matches!(value, Value::Instance(_) | Value::Record(_) | Value::Variant(_)) {
+                    return Ok(false);
+                }
+                if let Some(class_name) = class_name {
+                    let same_type = match (env.get(class_name), value) {
+                        (Ok(Value::Class(class)), Value::Instance(instance)) => {
+                            is_subclass(&instance.class, &class)
+                        }
+                        (Ok(Value::RecordType(record)), Value::Record(value)) => Rc::ptr_eq(&record, &value.record),
+                        (Ok(Value::Class(_) | Value::RecordType(_)), _) => false,
+                        (Ok(_), _) => {
+                            return Err((
+                                format!("'{}' neni trida ani zaznam.", class_name.lexeme),
+                                class_name.clone(),
+                            ))
+                        }
+                        (Err(msg), _) => return Err((msg, class_name.clone())),
+                    };
+                    if !same_type {
+                        return Ok(false);
+                    }
+                }
+                for (field, pattern) in fields {
+                    let field_value = match field_value(value, &field.lexeme) {
+                        Some(field_value) => field_value,
+                        None => return Ok(false),
+                    };
+                    if !pattern.matches(&field_value, env, bindings)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Pattern::Variant(owner, name, elements) => {
+                let owner_enum = match env.get(owner) {
+                    Ok(Value::Enum(owner_enum)) => owner_enum,
+                    Ok(_) => return Err((format!("'{}' neni vycet.", owner.lexeme), owner.clone())),
+                    Err(msg) => return Err((msg, owner.clone())),
+                };
+                if owner_enum.position(&name.lexeme).is_none() {
+                    return Err((
+                        format!("Vycet {} nema variantu '{}'.", owner.lexeme, name.lexeme),
+                        name.clone(),
+                    ));
+                }
+                let variant = match value {
+                    Value::Variant(variant)
+                        if Rc::ptr_eq(&variant.owner, &owner_enum) && variant.name() == name.lexeme =>
+                    {
+                        variant
+                    }
+                    _ => return Ok(false),
+                };
+                let elements = match elements {
+                    Some(elements) => elements,
+                    None => return Ok(true),
+                };
+                check_variant_length(variant, elements, name)?;
+                for (pattern, item) in elements.iter().zip(&variant.values) {
+                    if !pattern.matches(item, env, bindings)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Pattern::Alternatives(alternatives) => {
+                for alternative in alternatives {
+                    let mut alternative_bindings = Vec::new();
+                    if alternative.matches(value, env, &mut alternative_bindings)? {
+                        bindings.extend(alternative_bindings);
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+        }
+    }
+
+    /// Vsechna jmena, ktera vzor pri uspechu definuje.
+    pub fn bindings(&self) -> Vec<Token> {
+        match self {
+            Pattern::Wildcard | Pattern::Literal(_) => Vec::new(),
+            Pattern::Binding(name) => vec![name.clone()],
+            Pattern::List(elements, rest) => elements
+                .iter()
+                .flat_map(|element| element.bindings())
+                .chain(rest.clone())
+                .collect(),
+            Pattern::Tuple(elements) => elements
+                .iter()
+                .flat_map(|element| element.bindings())
+                .collect(),
+            Pattern::Instance(_, fields) => fields
+                .iter()
+                .flat_map(|(_, field)| field.bindings())
+                .collect(),
+            Pattern::Variant(_, _, elements) => elements
+                .iter()
+                .flatten()
+                .flat_map(|element| element.bindings())
+                .collect(),
+            Pattern::Alternatives(alternatives) => alternatives
+                .iter()
+                .flat_map(|alternative| alternative.bindings())
+                .collect(),
+        }
+    }
+
+    /// Rozlozi hodnotu podle vzoru pro `var [a, b] = ...` a podobne. Na rozdil
+    /// od `matches` neodpovidajici tvar hodnoty neni neuspech, ale chyba.
+    pub fn destructure(
+        &self,
+        value: &Value,
+        token: &Token,
+        bindings: &mut Vec<(Token, Value)>,
+    ) -> Result<(), (String, Token)> {
+        match self {
+            Pattern::Wildcard => Ok(()),
+            Pattern::Binding(name) => {
+                bindings.push((name.clone(), value.clone()));
+                Ok(())
+            }
+            Pattern::List(elements, rest) => {
+                let items = match value {
+                    Value::List(a) => a.borrow().clone(),
+                    _ => {
+                        return Err((
+                            format!("Hodnotu {} nelze rozlozit jako seznam.", value),
+                            token.clone(),
+                        ))
+                    }
+                };
+                let expected = match rest {
+                    None if items.len() != elements.len() => format!("{}", elements.len()),
+                    Some(_) if items.len() < elements.len() => format!("alespon {}", elements.len()),
+                    _ => String::new(),
+                };
+                if !expected.is_empty() {
+                    return Err((
+                        format!("Ocekavano {} prvku ale seznam jich ma {}.", expected, items.len()),
+                        token.clone(),
+                    ));
+                }
+                for (pattern, item) in elements.iter().zip(&items) {
+                    pattern.destructure(item, token, bindings)?;
+                }
+                if let Some(rest) = rest {
+                    let remaining = items[elements.len()..].to_vec();
+                    bindings.push((rest.clone(), Value::List(Rc::new(RefCell::new(remaining)))));
+                }
+                Ok(())
+            }
+            Pattern::Tuple(elements) => {
+                let items = match value {
+                    Value::Tuple(items) => items,
+                    _ => {
+                        return Err((
+                            format!("Hodnotu {} nelze rozlozit jako ntici.", value),
+                            token.clone(),
+                        ))
+                    }
+                };
+                if items.len() != elements.len() {
+                    return Err((
+                        format!("Ocekavano {} prvku ale ntice jich ma {}.", elements.len(), items.len()),
+                        token.clone(),
+                    ));
+                }
+                for (pattern, item) in elements.iter().zip(items.iter()) {
+                    pattern.destructure(item, token, bindings)?;
+                }
+                Ok(())
+            }
+            Pattern::Instance(None, fields) => {
+                if !matches!(value, Value::Instance(_) | Value::Record(_) | Value::Variant(_)) {
+                    return Err((
+                        format!("Hodnotu {} nelze rozlozit podle vlastnosti.", value),
+                        token.clone(),
+                    ));
+                }
+                for (field, pattern) in fields {
+                    let field_value = match field_value(value, &field.lexeme) {
+                        Some(field_value) => field_value,
+                        None => {
+                            return Err((
+                                format!("{} nema vlastnost '{}'.", value, field.lexeme),
+                                field.clone(),
+                            ))
+                        }
+                    };
+                    pattern.destructure(&field_value, token, bindings)?;
+                }
+                Ok(())
+            }
+            Pattern::Variant(owner, name, elements) => {
+                let variant = match value {
+                    Value::Variant(variant) if variant.is(&owner.lexeme, &name.lexeme) => variant,
+                    _ => {
+                        return Err((
+                            format!("Hodnota {} neni varianta {}.{}.", value, owner.lexeme, name.lexeme),
+                            token.clone(),
+                        ))
+                    }
+                };
+                if let Some(elements) = elements {
+                    check_variant_length(variant, elements, name)?;
+                    for (pattern, item) in elements.iter().zip(&variant.values) {
+                        pattern.destructure(item, token, bindings)?;
+                    }
+                }
+                Ok(())
+            }
+            _ => Err((
+                String::from("Tento vzor nelze pouzit pro rozlozeni."),
+                token.clone(),
+            )),
+        }
+    }
+}
+
+/// Pole instance, zaznamu nebo data varianty pro vzory s vlastnostmi.
+fn field_value(value: &Value, name: &str) -> Option<Value> {
+    match value {
+        Value::Instance(instance) => instance.fields.borrow().get(name).cloned(),
+        Value::Record(value) => value.field(name),
+        Value::Variant(variant) => variant.field(name),
+        _ => None,
+    }
+}
+
+fn check_variant_length(
+    variant: &VariantValue,
+    elements: &[Pattern],
+    name: &Token,
+) -> Result<(), (String, Token)> {
+    if elements.len() == variant.values.len() {
+        return Ok(());
+    }
+    Err((
+        format!(
+            "Varianta {} ma {} hodnot, ale vzor jich ma {}.",
+            name.lexeme,
+            variant.values.len(),
+            elements.len()
+        ),
+        name.clone(),
+    ))
+}
+
+/// Jestli je `class` primo trida `ancestor`, nebo z ni dedi. Tridy se
+/// porovnavaji podle identity, stejne pojmenovana jina trida nestaci.
+pub(crate) fn is_subclass(class: &Rc<Class>, ancestor: &Rc<Class>) -> bool {
+    if Rc::ptr_eq(class, ancestor) {
+        return true;
+    }
+    match &class.super_class {
+        Some(super_class) => is_subclass(super_class, ancestor),
+        None => false,
+    }
+}
+
+/// Jestli trida nebo nektera z jejich nadtrid pouziva trait `used`.
+pub(crate) fn uses_trait(class: &Class, used: &Rc<Trait>) -> bool {
+    if class.traits.iter().any(|other| Rc::ptr_eq(other, used)) {
+        return true;
+    }
+    match &class.super_class {
+        Some(super_class) => uses_trait(super_class, used),
+        None => false,
+    }
+}
+
+/// Najde prvni vetev, jejiz vzor i podminka odpovida hodnote, a vrati ji
+/// spolu s prostredim, ve kterem jsou definovane zachycene promenne.
+pub fn select_arm<'a, T>(
+    keyword: &Token,
+    value: &Value,
+    arms: &'a [MatchArm<T>],
+    env: &Rc<Environment>,
+) -> Result<(&'a T, Rc<Environment>), (String, Token)> {
+    for arm in arms {
+        let mut bindings = Vec::new();
+        if !arm.pattern.matches(value, env, &mut bindings)? {
+            continue;
+        }
+        let arm_environment = Rc::new(Environment::new_child(Rc::clone(env)));
+        for (name, bound) in bindings {
+            arm_environment.define(name, bound);
+        }
+        if let Some(guard) = &arm.guard {
+            let passed = is_truth(guard.evaluate(Rc::clone(&arm_environment))?, false)?;
+            if passed != Value::Bool(true) {
+                continue;
+            }
+        }
+        return Ok((&arm.body, arm_environment));
+    }
+    Err((
+        format!("Zadna vetev 'match' neodpovida hodnote {}.", value),
+        keyword.clone(),
+    ))
+}
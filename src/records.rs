@@ -0,0 +1,118 @@
+use crate::expressions::Literal;
+use crate::moonenv::Environment;
+use crate::token::Token;
+use crate::value::{Callable, Parameter, Value};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+/// Zaznam z `record Bod(x, y);`. Volani zaznamu vytvori nemennou hodnotu,
+/// parametry funguji jako u funkci (pojmenovane argumenty, vychozi hodnoty
+/// i anotace).
+pub struct Record {
+    pub(crate) name: Token,
+    pub(crate) parameters: Vec<Parameter>,
+    pub(crate) environment: Rc<Environment>,
+}
+
+/// Hodnota zaznamu. Pole jsou ve stejnem poradi jako parametry zaznamu.
+#[derive(Debug)]
+pub struct RecordValue {
+    pub(crate) record: Rc<Record>,
+    pub(crate) values: Vec<Value>,
+}
+
+impl fmt::Debug for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Record")
+            .field("name", &self.name.lexeme)
+            .field("fields", &self.field_names().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Record {
+    /// Konstruktor zaznamu jako funkce, aby volani sdilelo kontrolu
+    /// a navazani argumentu s ostatnimi funkcemi.
+    pub fn constructor(self: &Rc<Self>) -> Rc<Callable> {
+        self.builder(self.parameters.clone(), format!("<record {}>", self.name.lexeme))
+    }
+
+    fn builder(self: &Rc<Self>, parameters: Vec<Parameter>, string: String) -> Rc<Callable> {
+        let record = Rc::clone(self);
+        Rc::new(Callable {
+            parameters,
+            function: Rc::new(move |values, _env| {
+                Ok(Value::Record(Rc::new(RecordValue {
+                    record: Rc::clone(&record),
+                    values,
+                })))
+            }),
+            string,
+            name: self.name.clone(),
+            environment: Rc::new(Environment::new_child(Rc::clone(&self.environment))),
+            is_initializer: RefCell::new(false),
+            return_type: None,
+        })
+    }
+
+    pub fn field_names(&self) -> impl Iterator<Item = &str> {
+        self.parameters.iter().map(|parameter| parameter.name.lexeme.as_str())
+    }
+}
+
+impl RecordValue {
+    pub fn field(&self, name: &str) -> Option<Value> {
+        let index = self.record.field_names().position(|field| field == name)?;
+        Some(self.values[index].clone())
+    }
+
+    /// Pole zaznamu, nebo `with`, ktere vrati kopii se zmenenymi poli.
+    pub fn get_property(self: &Rc<Self>, name: &Token) -> Result<Value, (String, Token)> {
+        if let Some(value) = self.field(&name.lexeme) {
+            return Ok(value);
+        }
+        if name.lexeme == "with" {
+            return Ok(Value::Function(self.with()));
+        }
+        Err((
+            format!("Zaznam {} nema pole '{}'.", self.record.name.lexeme, name.lexeme),
+            name.clone(),
+        ))
+    }
+
+    /// `bod.with(x: 5)`: vychozi hodnota kazdeho parametru je soucasna
+    /// hodnota pole, takze staci zadat jen pole, ktera se meni.
+    fn with(&self) -> Rc<Callable> {
+        let parameters = self
+            .record
+            .parameters
+            .iter()
+            .zip(&self.values)
+            .map(|(parameter, value)| Parameter {
+                default: Some(Rc::new(Literal { value: value.clone() })),
+                ..parameter.clone()
+            })
+            .collect();
+        self.record.builder(parameters, String::from("<native fn>"))
+    }
+}
+
+impl PartialEq for RecordValue {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.record, &other.record) && self.values == other.values
+    }
+}
+
+impl fmt::Display for RecordValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(", self.record.name.lexeme)?;
+        for (i, (field, value)) in self.record.field_names().zip(&self.values).enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {}", field, value)?;
+        }
+        write!(f, ")")
+    }
+}
@@ -1,3 +1,4 @@
+use crate::bigint::BigInt;
 use crate::token::Token;
 use crate::tokentype::TokenType;
 use crate::value::Value;
@@ -9,25 +10,34 @@ pub struct Scanner {
     start: usize,
     current: usize,
     line: usize,
+    pub(crate) allows: Vec<(u64, String)>,
 }
 
 static KEYWORDS: phf::Map<&'static str, TokenType> = phf_map! {
 "and" => TokenType::And,
 "class" => TokenType::Class,
+"const" => TokenType::Const,
 "else" => TokenType::Else,
+"enum" => TokenType::Enum,
 "false" => TokenType::False,
 "for" => TokenType::For,
 "fun" => TokenType::Fun,
 "if" => TokenType::If,
+"in" => TokenType::In,
+"match" => TokenType::Match,
 "nil" => TokenType::Nil,
+"not" => TokenType::Not,
 "or" => TokenType::Or,
 "print" => TokenType::Print,
+"record" => TokenType::Record,
 "return" => TokenType::Return,
 "super" => TokenType::Super,
 "this" => TokenType::This,
+"trait" => TokenType::Trait,
 "true" => TokenType::True,
 "var" => TokenType::Var,
 "while" => TokenType::While,
+"yield" => TokenType::Yield,
 };
 
 impl Scanner {
@@ -38,6 +48,7 @@ impl Scanner {
             start: 0,
             current: 0,
             line: 1,
+            allows: Vec::new(),
         }
     }
 
@@ -63,12 +74,90 @@ impl Scanner {
             ')' => self.add_token(TokenType::RightParen),
             '{' => self.add_token(TokenType::LeftBrace),
             '}' => self.add_token(TokenType::RightBrace),
+            '#' => {
+                if !self.match_char('{') {
+                    return Err((self.line as u64, String::from("unexpected character.")));
+                }
+                self.add_token(TokenType::HashLeftBrace);
+            }
+            '[' => self.add_token(TokenType::LeftBracket),
+            ']' => self.add_token(TokenType::RightBracket),
             ',' => self.add_token(TokenType::Comma),
-            '.' => self.add_token(TokenType::Dot),
-            '-' => self.add_token(TokenType::Minus),
-            '+' => self.add_token(TokenType::Plus),
+            '.' => {
+                let token_type = if !self.match_char('.') {
+                    TokenType::Dot
+                } else if self.match_char('.') {
+                    TokenType::DotDotDot
+                } else if self.match_char('=') {
+                    TokenType::DotDotEqual
+                } else {
+                    TokenType::DotDot
+                };
+                self.add_token(token_type);
+            }
+            '-' => {
+                let token_type = if self.match_char('=') {
+                    TokenType::MinusEqual
+                } else if self.match_char('-') {
+                    TokenType::MinusMinus
+                } else if self.match_char('>') {
+                    TokenType::Arrow
+                } else {
+                    TokenType::Minus
+                };
+                self.add_token(token_type);
+            }
+            '+' => {
+                let token_type = if self.match_char('=') {
+                    TokenType::PlusEqual
+                } else if self.match_char('+') {
+                    TokenType::PlusPlus
+                } else {
+                    TokenType::Plus
+                };
+                self.add_token(token_type);
+            }
             ';' => self.add_token(TokenType::SemiColon),
-            '*' => self.add_token(TokenType::Star),
+            ':' => self.add_token(TokenType::Colon),
+            '?' => {
+                let token_type = if self.match_char('?') {
+                    TokenType::QuestionQuestion
+                } else if self.match_char('.') {
+                    TokenType::QuestionDot
+                } else {
+                    TokenType::Question
+                };
+                self.add_token(token_type);
+            }
+            '%' => {
+                let following = self.match_char('=');
+                self.add_token(if following {
+                    TokenType::PercentEqual
+                } else {
+                    TokenType::Percent
+                });
+            }
+            '&' => self.add_token(TokenType::Ampersand),
+            '|' => self.add_token(TokenType::Pipe),
+            '^' => self.add_token(TokenType::Caret),
+            '*' => {
+                let token_type = if self.match_char('*') {
+                    TokenType::StarStar
+                } else if self.match_char('=') {
+                    TokenType::StarEqual
+                } else {
+                    TokenType::Star
+                };
+                self.add_token(token_type);
+            }
+            '~' => {
+                let following = self.match_char('/');
+                self.add_token(if following {
+                    TokenType::TildeSlash
+                } else {
+                    TokenType::Tilde
+                });
+            }
             '!' => {
                 let following = self.match_char('=');
                 self.add_token(if following {
@@ -78,28 +167,34 @@ impl Scanner {
                 });
             }
             '=' => {
-                let following = self.match_char('=');
-                self.add_token(if following {
+                let token_type = if self.match_char('=') {
                     TokenType::EqualEqual
+                } else if self.match_char('>') {
+                    TokenType::FatArrow
                 } else {
                     TokenType::Equal
-                });
+                };
+                self.add_token(token_type);
             }
             '<' => {
-                let following = self.match_char('=');
-                self.add_token(if following {
-                    TokenType::LessEqual 
+                let token_type = if self.match_char('=') {
+                    TokenType::LessEqual
+                } else if self.match_char('<') {
+                    TokenType::LessLess
                 } else {
-                    TokenType::Less 
-                });
+                    TokenType::Less
+                };
+                self.add_token(token_type);
             }
             '>' => {
-                let following = self.match_char('=');
-                self.add_token(if following {
-                    TokenType::GreaterEqual 
+                let token_type = if self.match_char('=') {
+                    TokenType::GreaterEqual
+                } else if self.match_char('>') {
+                    TokenType::GreaterGreater
                 } else {
-                    TokenType::Greater 
-                });
+                    TokenType::Greater
+                };
+                self.add_token(token_type);
             }
             '/' => {
                 let following = self.match_char('/');
@@ -109,6 +204,9 @@ impl Scanner {
                         self.advance();
                         next = self.peek();
                     }
+                    self.lint_comment();
+                } else if self.match_char('=') {
+                    self.add_token(TokenType::SlashEqual);
                 } else {
                     self.add_token(TokenType::Slash);
                 }
@@ -159,10 +257,15 @@ impl Scanner {
             while is_digit(self.peek()) {
                 self.advance();
             }
+            let number_string = &self.source[self.start..self.current];
+            let number: f64 = number_string.parse().unwrap();
+            self.add_token_final(TokenType::Number, Value::Number(number));
+            return;
         }
+
         let number_string = &self.source[self.start..self.current];
-        let number: f64 = number_string.parse().unwrap();
-        self.add_token_final(TokenType::Number, Value::Number(number));
+        let number = Value::integer(BigInt::parse(number_string).unwrap());
+        self.add_token_final(TokenType::Number, number);
     }
     fn identifier(&mut self) {
         while is_alphanumeric(self.peek()) {
@@ -224,6 +327,25 @@ impl Scanner {
         }
         self.source.chars().nth(self.current + 1).unwrap()
     }
+
+    /// Komentar `// moon:allow(unused, shadowing)` vypne vyjmenovana varovani.
+    /// Za kodem plati pro svuj radek, samostatny pro radek pod nim.
+    fn lint_comment(&mut self) {
+        let comment = self.source[self.start + 2..self.current].trim();
+        let names = match comment
+            .strip_prefix("moon:allow(")
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            Some(names) => names,
+            None => return,
+        };
+        let line = self.line as u64;
+        let standalone = self.tokens.last().is_none_or(|token| token.line < line);
+        let line = if standalone { line + 1 } else { line };
+        for name in names.split(',') {
+            self.allows.push((line, name.trim().to_string()));
+        }
+    }
 }
 
 fn is_alphanumeric(c: char) -> bool {
@@ -0,0 +1,213 @@
+use crate::natives::native_method;
+use crate::token::Token;
+use crate::value::Value;
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+/// Mnozina z `#{1, 2}` nebo `set(...)`. Prvky si pamatuji poradi vlozeni,
+/// aby vypis i prochazeni nezavisely na hashovani.
+#[derive(Debug, Clone, Default)]
+pub struct SetValue {
+    items: Vec<Value>,
+    /// Indexy prvku podle hashe. Shodu uvnitr skupiny urci `Value::equals`,
+    /// aby se chyba z `__eq__` neztratila.
+    buckets: HashMap<u64, Vec<usize>>,
+}
+
+impl SetValue {
+    pub fn from_values(values: Vec<Value>, token: &Token) -> Result<SetValue, (String, Token)> {
+        let mut set = SetValue::default();
+        for value in values {
+            set.insert(value, token)?;
+        }
+        Ok(set)
+    }
+
+    /// Vlozi prvek, vrati jestli v mnozine jeste nebyl.
+    pub fn insert(&mut self, value: Value, token: &Token) -> Result<bool, (String, Token)> {
+        if !is_hashable(&value) {
+            return Err((
+                format!("Hodnotu {} nelze vlozit do mnoziny, muze se zmenit.", value),
+                token.clone(),
+            ));
+        }
+        let hash = hash(&value)?;
+        if self.find(hash, &value)?.is_some() {
+            return Ok(false);
+        }
+        self.buckets.entry(hash).or_default().push(self.items.len());
+        self.items.push(value);
+        Ok(true)
+    }
+
+    pub fn remove(&mut self, value: &Value) -> Result<bool, (String, Token)> {
+        if !is_hashable(value) {
+            return Ok(false);
+        }
+        let index = match self.find(hash(value)?, value)? {
+            Some(index) => index,
+            None => return Ok(false),
+        };
+        self.items.remove(index);
+        for indices in self.buckets.values_mut() {
+            indices.retain(|&other| other != index);
+            for other in indices.iter_mut().filter(|other| **other > index) {
+                *other -= 1;
+            }
+        }
+        self.buckets.retain(|_, indices| !indices.is_empty());
+        Ok(true)
+    }
+
+    pub fn contains(&self, value: &Value) -> Result<bool, (String, Token)> {
+        if !is_hashable(value) {
+            return Ok(false);
+        }
+        Ok(self.find(hash(value)?, value)?.is_some())
+    }
+
+    fn find(&self, hash: u64, value: &Value) -> Result<Option<usize>, (String, Token)> {
+        for &index in self.buckets.get(&hash).into_iter().flatten() {
+            if self.items[index].equals(value)? {
+                return Ok(Some(index));
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn items(&self) -> &[Value] {
+        &self.items
+    }
+
+    pub fn union(&self, other: &SetValue, token: &Token) -> Result<SetValue, (String, Token)> {
+        let mut result = self.clone();
+        for item in &other.items {
+            result.insert(item.clone(), token)?;
+        }
+        Ok(result)
+    }
+
+    pub fn intersection(&self, other: &SetValue, token: &Token) -> Result<SetValue, (String, Token)> {
+        self.filter(token, |item| other.contains(item))
+    }
+
+    pub fn difference(&self, other: &SetValue, token: &Token) -> Result<SetValue, (String, Token)> {
+        self.filter(token, |item| Ok(!other.contains(item)?))
+    }
+
+    fn filter(
+        &self,
+        token: &Token,
+        keep: impl Fn(&Value) -> Result<bool, (String, Token)>,
+    ) -> Result<SetValue, (String, Token)> {
+        let mut result = SetValue::default();
+        for item in &self.items {
+            if keep(item)? {
+                result.insert(item.clone(), token)?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Na poradi prvku nezalezi.
+    pub fn equals(&self, other: &SetValue) -> Result<bool, (String, Token)> {
+        if self.items.len() != other.items.len() {
+            return Ok(false);
+        }
+        for item in &self.items {
+            if !other.contains(item)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    pub fn into_value(self) -> Value {
+        Value::Set(Rc::new(RefCell::new(self)))
+    }
+
+/// Metody mnoziny: `add`, `remove`, `contains` a mnozinove operace,
+    /// ktere jdou zapsat i operatory `|`, `&` a `-`.
+    pub fn get_property(set: &Rc<RefCell<SetValue>>, name: &Token) -> Result<Value, (String, Token)> {
+        let set = Rc::clone(set);
+        let token = name.clone();
+        match &*name.lexeme {
+            "add" => Ok(native_method("add", &["value"], move |arguments| {
+                let added = set.borrow_mut().insert(arguments[0].clone(), &token)?;
+                Ok(Value::Bool(added))
+            })),
+            "remove" => Ok(native_method("remove", &["value"], move |arguments| {
+                Ok(Value::Bool(set.borrow_mut().remove(&arguments[0])?))
+            })),
+            "contains" => Ok(native_method("contains", &["value"], move |arguments| {
+                Ok(Value::Bool(set.borrow().contains(&arguments[0])?))
+            })),
+            "union" | "intersection" | "difference" => {
+                let operation = match &*name.lexeme {
+                    "union" => SetValue::union,
+                    "intersection" => SetValue::intersection,
+                    _ => SetValue::difference,
+                };
+                Ok(native_method(&name.lexeme, &["other"], move |arguments| match &arguments[0] {
+                    Value::Set(other) => Ok(operation(&set.borrow(), &other.borrow(), &token)?.into_value()),
+                    other => Err((format!("Ocekavam mnozinu, ne {}.", other), token.clone())),
+                }))
+            }
+            _ => Err((
+                format!("Mnozina nema vlastnost '{}'.", name.lexeme),
+                name.clone(),
+            )),
+        }
+    }
+}
+
+/// Seznamy a mnoziny se mohou menit, po zmene by je mnozina uz nenasla.
+/// Ntice, zaznamy a varianty jdou pouzit, jen kdyz jsou takove i vsechny
+/// jejich hodnoty. Instance s `__eq__` bez `__hash__` by se rovnaly, ale
+/// mely ruzny hash.
+fn is_hashable(value: &Value) -> bool {
+    match value {
+        Value::List(_) | Value::Set(_) => false,
+        Value::Instance(instance) => {
+            instance.class.find_method(String::from("__eq__")).is_none()
+                || instance.class.find_method(String::from("__hash__")).is_some()
+        }
+        Value::Tuple(items) => items.iter().all(is_hashable),
+        Value::Record(record) => record.values.iter().all(is_hashable),
+        Value::Variant(variant) => variant.values.iter().all(is_hashable),
+        _ => true,
+    }
+}
+
+/// Na poradi prvku nezalezi. `__eq__` instanci se tu nevola, to dela
+/// `SetValue::equals`.
+impl PartialEq for SetValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.items.len() == other.items.len() && self.items.iter().all(|item| other.items.contains(item))
+    }
+}
+
+impl fmt::Display for SetValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{{")?;
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", item)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// Hash prvku. Chyba z `__hash__` se vrati driv, nez ji `Hash` ztrati.
+fn hash(value: &Value) -> Result<u64, (String, Token)> {
+    value.check_hash()?;
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    Ok(hasher.finish())
+}
@@ -1,8 +1,15 @@
-use crate::moonenv::Environment;
+use crate::checker::Checker;
+use crate::moonenv::{check_variable, Environment};
 use crate::expressions::{is_truth, Expr, Kind};
-use crate::value::{Callable, Class, Value};
+use crate::enums::Enum;
+use crate::records::Record;
+use crate::value::{Callable, Class, Field, Parameter, Trait, Value};
 use crate::token::Token;
+use crate::generator::Generator;
 use crate::interpreter::Interpreter;
+use crate::iteration::MoonIterator;
+use crate::patterns::{select_arm, MatchArm, Pattern};
+use crate::types::{Annotation, Type};
 use std::borrow::Borrow;
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -11,18 +18,25 @@ use std::rc::Rc;
 pub trait Statement {
     fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)>;
     fn kind(&self) -> StatementKind;
+    fn check(&self, checker: &mut Checker);
 }
 
 pub enum StatementKind {
     Expression,
     Print,
     Var,
-    Block,
-    If,
-    While,
+    Block(Vec<Rc<dyn Statement>>),
+    If(Rc<dyn Expr>, Rc<dyn Statement>, Option<Rc<dyn Statement>>),
+    While(Rc<dyn Expr>, Rc<dyn Statement>),
+    ForIn(Token, Vec<Token>, Rc<dyn Expr>, Rc<dyn Statement>),
     Function(Function),
     ReturnStatement,
+    Yield(Rc<dyn Expr>),
+    Match(Token, Rc<dyn Expr>, Rc<Vec<MatchArm<Rc<dyn Statement>>>>),
     ClassStatement,
+    TraitStatement,
+    EnumStatement,
+    RecordStatement,
 }
 
 pub struct Expression {
@@ -31,13 +45,13 @@ pub struct Expression {
 
 impl Statement for Expression {
     fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
-        match self.expression.evaluate(env) {
-            Ok(value) => {
-                println!("{}", value);
-                Ok(Value::None)
-            }
-            Err(err) => Err(err),
-        }
+        let value = self.expression.evaluate(env)?;
+        println!("{}", value.to_text()?);
+        Ok(Value::None)
+    }
+
+    fn check(&self, checker: &mut Checker) {
+        self.expression.check(checker);
     }
 
     fn kind(&self) -> StatementKind {
@@ -51,13 +65,13 @@ pub struct Print {
 
 impl Statement for Print {
     fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
-        match self.expressions.evaluate(env) {
-            Ok(value) => {
-                println!("{}", value);
-                Ok(Value::None)
-            }
-            Err(err) => Err(err),
-        }
+        let value = self.expressions.evaluate(env)?;
+        println!("{}", value.to_text()?);
+        Ok(Value::None)
+    }
+
+    fn check(&self, checker: &mut Checker) {
+        self.expressions.check(checker);
     }
 
     fn kind(&self) -> StatementKind {
@@ -68,20 +82,98 @@ impl Statement for Print {
 pub struct Var {
     pub(crate) name: Token,
     pub(crate) init: Rc<dyn Expr>,
+    pub(crate) constant: bool,
+    pub(crate) annotation: Option<Annotation>,
 }
 
 impl Statement for Var {
     fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
         let val = self.init.evaluate(Rc::clone(&env))?;
-        env.define(self.name.lexeme.clone(), val.clone());
+        declare(&env, &self.name, val.clone(), self.constant, self.annotation.as_ref())?;
         Ok(val.clone())
     }
 
+    fn check(&self, checker: &mut Checker) {
+        let value = self.init.check(checker);
+        let declared = match &self.annotation {
+            Some(annotation) => {
+                let expected = checker.annotation(Some(annotation));
+                checker.expect(&expected, &value, &self.name, || {
+                    format!("Promenna '{}' ma typ {}, ale hodnota je {}.", self.name.lexeme, expected, value)
+                });
+                expected
+            }
+            // Konstantu nejde prepsat, takze jeji typ je typ pocatecni hodnoty.
+            None if self.constant => value,
+            None => Type::Any,
+        };
+        checker.declare(&self.name.lexeme, declared);
+    }
+
     fn kind(&self) -> StatementKind {
         StatementKind::Var
     }
 }
 
+pub struct DestructuringVar {
+    pub(crate) pattern: Pattern,
+    pub(crate) token: Token,
+    pub(crate) init: Rc<dyn Expr>,
+    pub(crate) constant: bool,
+}
+
+impl Statement for DestructuringVar {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let value = self.init.evaluate(Rc::clone(&env))?;
+        let mut bindings = Vec::new();
+        self.pattern.destructure(&value, &self.token, &mut bindings)?;
+        for (name, bound) in bindings {
+            declare(&env, &name, bound, self.constant, None)?;
+        }
+        Ok(value)
+    }
+
+    fn check(&self, checker: &mut Checker) {
+        self.init.check(checker);
+        for name in self.pattern.bindings() {
+            checker.declare(&name.lexeme, Type::Any);
+        }
+    }
+
+    fn kind(&self) -> StatementKind {
+        StatementKind::Var
+    }
+}
+
+/// Definuje jmeno z deklarace. Obycejna promenna nesmi nahradit konstantu
+/// ze stejneho prostredi, konstanta ano, takze v REPL jde definici zopakovat.
+/// S `--strict-types` se hodnota zkontroluje proti anotaci.
+fn declare(
+    env: &Environment,
+    name: &Token,
+    value: Value,
+    constant: bool,
+    annotation: Option<&Annotation>,
+) -> Result<(), (String, Token)> {
+    let annotation = annotation.filter(|_| env.strict_types);
+    if constant {
+        if let Some(annotation) = annotation {
+            check_variable(name, &annotation.ty, &value)?;
+        }
+        env.define_constant(name.lexeme.clone(), value);
+    } else if env.is_constant(&name.lexeme) {
+        return Err((
+            format!("Nelze znovu deklarovat konstantu '{}'.", name.lexeme),
+            name.clone(),
+        ));
+    } else if let Some(annotation) = annotation {
+        env.define_typed(name, value, annotation.ty.clone())?;
+    } else {
+        env.define(name.lexeme.clone(), value);
+    }
+    Ok(())
+}
+
 pub struct Block {
     pub(crate) statements: Vec<Rc<dyn Statement>>,
 }
@@ -100,8 +192,16 @@ impl Statement for Block {
         Ok(Value::None)
     }
 
+    fn check(&self, checker: &mut Checker) {
+        checker.begin_scope();
+        for statement in &self.statements {
+            statement.check(checker);
+        }
+        checker.end_scope();
+    }
+
     fn kind(&self) -> StatementKind {
-        StatementKind::Block
+        StatementKind::Block(self.statements.clone())
     }
 }
 
@@ -122,8 +222,20 @@ impl Statement for If {
         }
     }
 
+    fn check(&self, checker: &mut Checker) {
+        self.condition.check(checker);
+        self.then_branch.check(checker);
+        if let Some(else_branch) = &self.else_branch {
+            else_branch.check(checker);
+        }
+    }
+
     fn kind(&self) -> StatementKind {
-        StatementKind::If
+        StatementKind::If(
+            Rc::clone(&self.condition),
+            Rc::clone(&self.then_branch),
+            self.else_branch.clone(),
+        )
     }
 }
 
@@ -146,54 +258,174 @@ impl Statement for While {
         Ok(Value::None)
     }
 
+    fn check(&self, checker: &mut Checker) {
+        self.condition.check(checker);
+        self.body.check(checker);
+    }
+
+    fn kind(&self) -> StatementKind {
+        StatementKind::While(Rc::clone(&self.condition), Rc::clone(&self.body))
+    }
+}
+
+pub struct ForIn {
+    pub(crate) keyword: Token,
+    pub(crate) names: Vec<Token>,
+    pub(crate) iterable: Rc<dyn Expr>,
+    pub(crate) body: Rc<dyn Statement>,
+}
+
+impl Statement for ForIn {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let iterable = self.iterable.evaluate(Rc::clone(&env))?;
+        let mut iterator = MoonIterator::new(iterable, &self.keyword)?;
+        while let Some(item) = iterator.next()? {
+            let loop_environment = Rc::new(Environment::new_child(Rc::clone(&env)));
+            bind_loop_variables(&loop_environment, &self.names, item)?;
+            if let Value::Return(a) = self.body.evaluate(loop_environment)? {
+                return Ok(Value::Return(a));
+            }
+        }
+        Ok(Value::None)
+    }
+
+    fn check(&self, checker: &mut Checker) {
+        let iterable = self.iterable.check(checker);
+        let element = checker.element(&iterable, &self.keyword);
+        checker.begin_scope();
+        for name in &self.names {
+            let ty = if self.names.len() == 1 { element.clone() } else { Type::Any };
+            checker.declare(&name.lexeme, ty);
+        }
+        self.body.check(checker);
+        checker.end_scope();
+    }
+
     fn kind(&self) -> StatementKind {
-        StatementKind::While
+        StatementKind::ForIn(
+            self.keyword.clone(),
+            self.names.clone(),
+            Rc::clone(&self.iterable),
+            Rc::clone(&self.body),
+        )
     }
 }
 
+pub(crate) fn bind_loop_variables(env: &Environment, names: &[Token], item: Value) -> Result<(), (String, Token)> {
+    if names.len() == 1 {
+        env.define(names[0].lexeme.clone(), item);
+        return Ok(());
+    }
+    let items = match &item {
+        Value::List(a) => (**a).borrow().clone(),
+        Value::Tuple(a) => a.to_vec(),
+        _ => Vec::new(),
+    };
+    if items.len() != names.len() {
+        return Err((
+            format!("Hodnotu {} nelze rozlozit do {} promennych.", item, names.len()),
+            names[0].clone(),
+        ));
+    }
+    for (name, value) in names.iter().zip(items) {
+        env.define(name.lexeme.clone(), value);
+    }
+    Ok(())
+}
+
 pub struct Function {
     pub(crate) name: Token,
-    pub(crate) parameters: Vec<Token>,
+    pub(crate) parameters: Vec<Parameter>,
     pub(crate) body: Vec<Rc<dyn Statement>>,
+    pub(crate) generator: bool,
+    pub(crate) constant: bool,
+    pub(crate) return_type: Option<Annotation>,
 }
 
 impl Statement for Function {
     fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
         let borrow: &Environment = env.borrow();
         let environment_clone = Rc::new(borrow.clone());
-        let cloned_body = self.body.clone();
-        let cloned_parameters = self.parameters.clone();
-        let function = Value::Function(Rc::new(Callable {
-            arity: self.parameters.len(),
-            function: Rc::new(move |arguments, environment| {
-                for (i, parameter) in cloned_parameters.iter().enumerate() {
-                    environment.define(
-                        parameter.lexeme.clone(),
-                        arguments.get(i).expect("error").clone(),
-                    );
-                }
-                let mut interpreter = Interpreter::new_with_env(Rc::clone(&environment));
-                interpreter.interpret(cloned_body.clone())
-            }),
-            string: format!("<fn {}>", self.name.lexeme),
-            name: self.name.clone(),
-            environment: Rc::clone(&environment_clone),
-            is_initializer: RefCell::new(false),
-        }));
-        env.define(self.name.lexeme.clone(), function.clone());
+        let function = Value::Function(Rc::new(make_function(
+            self.name.clone(),
+            &self.parameters,
+            &self.body,
+            format!("<fn {}>", self.name.lexeme),
+            environment_clone,
+            self.generator,
+            self.return_type.clone(),
+        )));
+        declare(&env, &self.name, function.clone(), self.constant, None)?;
         Ok(function)
     }
 
+    fn check(&self, checker: &mut Checker) {
+        let signature = checker.signature(
+            &self.name.lexeme,
+            &self.parameters,
+            self.return_type.as_ref(),
+            self.generator,
+        );
+        checker.declare(&self.name.lexeme, Type::Function(Some(Rc::clone(&signature))));
+        checker.function_body(&self.name, &signature, &self.body, self.generator);
+    }
+
     fn kind(&self) -> StatementKind {
         StatementKind::Function(Function {
             name: self.name.clone(),
             parameters: self.parameters.clone(),
             body: self.body.clone(),
+            generator: self.generator,
+            constant: self.constant,
+            return_type: self.return_type.clone(),
         })
     }
 }
 
+pub(crate) fn make_function(
+    name: Token,
+    parameters: &[Parameter],
+    body: &[Rc<dyn Statement>],
+    string: String,
+    environment: Rc<Environment>,
+    generator: bool,
+    return_type: Option<Annotation>,
+) -> Callable {
+    let cloned_body = body.to_vec();
+    let cloned_parameters = parameters.to_vec();
+    let generator_name = name.lexeme.clone();
+    Callable {
+        parameters: parameters.to_vec(),
+        function: Rc::new(move |arguments, environment| {
+            // Kazde volani dostane vlastni ramec, aby si generatory a lambdy
+            // z ruznych volani stejne funkce neprepisovaly promenne.
+            let frame = Rc::new(Environment::new_child(environment));
+            for (parameter, argument) in cloned_parameters.iter().zip(arguments) {
+                match &parameter.annotation {
+                    // S `--strict-types` se kontroluje i prirazeni do parametru.
+                    Some(annotation) if frame.strict_types => {
+                        frame.define_typed(&parameter.name, argument, annotation.ty.clone())?
+                    }
+                    _ => frame.define(parameter.name.lexeme.clone(), argument),
+                }
+            }
+            if generator {
+                let generator = Generator::new(generator_name.clone(), cloned_body.clone(), frame);
+                return Ok(Value::Generator(Rc::new(generator)));
+            }
+            let mut interpreter = Interpreter::new_with_env(frame);
+            interpreter.interpret(cloned_body.clone())
+        }),
+        string,
+        name,
+        environment,
+        is_initializer: RefCell::new(false),
+        return_type,
+    }
+}
+
 pub struct ReturnStatement {
+    pub(crate) keyword: Token,
     pub(crate) value: Rc<dyn Expr>,
 }
 
@@ -205,15 +437,84 @@ impl Statement for ReturnStatement {
         }
     }
 
+    fn check(&self, checker: &mut Checker) {
+        let value = self.value.check(checker);
+        checker.return_value(&self.keyword, &value);
+    }
+
     fn kind(&self) -> StatementKind {
         StatementKind::ReturnStatement
     }
 }
 
+pub struct Yield {
+    pub(crate) keyword: Token,
+    pub(crate) value: Rc<dyn Expr>,
+}
+
+impl Statement for Yield {
+    fn evaluate(&self, _env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        Err((
+            String::from("'yield' lze pouzit jen uvnitr generatoru."),
+            self.keyword.clone(),
+        ))
+    }
+
+    fn check(&self, checker: &mut Checker) {
+        self.value.check(checker);
+    }
+
+    fn kind(&self) -> StatementKind {
+        StatementKind::Yield(Rc::clone(&self.value))
+    }
+}
+
+pub struct MatchStatement {
+    pub(crate) keyword: Token,
+    pub(crate) value: Rc<dyn Expr>,
+    pub(crate) arms: Rc<Vec<MatchArm<Rc<dyn Statement>>>>,
+}
+
+impl Statement for MatchStatement {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let value = self.value.evaluate(Rc::clone(&env))?;
+        let (body, arm_environment) = select_arm(&self.keyword, &value, &self.arms, &env)?;
+        body.evaluate(arm_environment)
+    }
+
+    fn check(&self, checker: &mut Checker) {
+        self.value.check(checker);
+        checker.match_arms(&self.arms, |body, checker| {
+            body.check(checker);
+            Type::Any
+        });
+    }
+
+    fn kind(&self) -> StatementKind {
+        StatementKind::Match(
+            self.keyword.clone(),
+            Rc::clone(&self.value),
+            Rc::clone(&self.arms),
+        )
+    }
+}
+
+/// Druh metody v tele tridy; `static`, `get` a `set` se pisou pred jmeno.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MethodKind {
+    Instance,
+    Static,
+    Getter,
+    Setter,
+}
+
 pub struct ClassStatement {
     pub(crate) name: Token,
-    pub(crate) methods: Vec<Rc<dyn Statement>>,
+    pub(crate) methods: Vec<(MethodKind, Rc<dyn Statement>)>,
+    pub(crate) fields: Vec<Field>,
     pub(crate) super_class: Option<Rc<dyn Expr>>,
+    pub(crate) traits: Vec<Token>,
+    pub(crate) constant: bool,
 }
 
 impl Statement for ClassStatement {
@@ -240,46 +541,211 @@ impl Statement for ClassStatement {
             }
         }
 
+        // Metody se definuji do vlastniho prostredi, aby jejich jmena
+        // neprepsala promenne (nebo konstanty) v okolnim bloku.
+        let method_environment = Rc::new(Environment::new_child(Rc::clone(&env)));
         let mut methods: HashMap<String, Value> = HashMap::new();
-        for method in &self.methods {
-            match method.kind() {
-                StatementKind::Function(function) => {
-                    let thing = function.evaluate(Rc::clone(&env))?;
-                    match thing {
-                        Value::Function(callable) => {
-                            if callable.name.lexeme == "init" {
-                                callable.set_initializer();
-                            }
-                            match possible_super_class {
-                                None => {}
-                                Some(ref a) => {
-                                    callable.bind_super(Value::Class(Rc::clone(&a)));
-                                }
-                            }
-                            methods.insert(
-                                function.name.lexeme.clone(),
-                                Value::Function(Rc::clone(&callable)),
-                            );
-                        }
-                        _ => {
-                            methods.insert(function.name.lexeme.clone(), thing.clone());
-                        }
+        let mut statics: HashMap<String, Rc<Callable>> = HashMap::new();
+        let mut getters: HashMap<String, Rc<Callable>> = HashMap::new();
+        let mut setters: HashMap<String, Rc<Callable>> = HashMap::new();
+        for (method_kind, method) in &self.methods {
+            let function = match method.kind() {
+                StatementKind::Function(function) => function,
+                _ => continue,
+            };
+            let callable = match function.evaluate(Rc::clone(&method_environment))? {
+                Value::Function(callable) => callable,
+                _ => continue,
+            };
+            let name = function.name.lexeme.clone();
+            if *method_kind == MethodKind::Instance && name == "init" {
+                callable.set_initializer();
+            }
+            if let (Some(a), false) = (&possible_super_class, *method_kind == MethodKind::Static) {
+                callable.bind_super(Value::Class(Rc::clone(a)));
+            }
+            match method_kind {
+                MethodKind::Instance => {
+                    methods.insert(name, Value::Function(callable));
+                }
+                MethodKind::Static => {
+                    statics.insert(name, callable);
+                }
+                MethodKind::Getter => {
+                    getters.insert(name, callable);
+                }
+                MethodKind::Setter => {
+                    setters.insert(name, callable);
+                }
+            }
+        }
+        let mut traits: Vec<Rc<Trait>> = Vec::new();
+        // Odkud prisla vychozi metoda, kterou trida sama nedefinuje.
+        let mut defaults: HashMap<String, Rc<Trait>> = HashMap::new();
+        for name in &self.traits {
+            let used = match env.get(name) {
+                Ok(Value::Trait(used)) => used,
+                Ok(_) => return Err((String::from("Za 'with' musi byt trait."), name.clone())),
+                Err(e) => return Err((e, name.clone())),
+            };
+            for (method, callable) in &used.methods {
+                match defaults.get(method) {
+                    Some(other) if !Rc::ptr_eq(other, &used) => {
+                        return Err((
+                            format!(
+                                "Trida {} dostava metodu '{}' z traitu {} i {}, musi ji definovat sama.",
+                                self.name.lexeme, method, other.name, used.name
+                            ),
+                            self.name.clone(),
+                        ))
+                    }
+                    Some(_) => {}
+                    None if methods.contains_key(method) => {}
+                    None => {
+                        methods.insert(method.clone(), Value::Function(Rc::clone(callable)));
+                        defaults.insert(method.clone(), Rc::clone(&used));
                     }
                 }
-                _ => {}
             }
+            traits.push(used);
         }
-        let class = Value::Class(Rc::new(Class {
-            arity: 0,
+        let arity = match methods.get("init") {
+            Some(Value::Function(init)) => init.parameters.len(),
+            _ => possible_super_class.as_ref().map_or(0, |a| a.arity),
+        };
+        let class = Rc::new(Class {
+            arity,
             name: self.name.lexeme.clone(),
             methods: RefCell::new(methods),
             super_class: possible_super_class,
-        }));
-        env.define(self.name.lexeme.clone(), class);
+            statics,
+            getters,
+            setters,
+            fields: self.fields.clone(),
+            traits,
+            environment: method_environment,
+        });
+        for used in &class.traits {
+            for method in &used.required {
+                if class.find_method(method.clone()).is_none() {
+                    return Err((
+                        format!(
+                            "Trida {} musi definovat metodu '{}' z traitu {}.",
+                            class.name, method, used.name
+                        ),
+                        self.name.clone(),
+                    ));
+                }
+            }
+        }
+        declare(&env, &self.name, Value::Class(class), self.constant, None)?;
         Ok(Value::None)
     }
 
+    fn check(&self, checker: &mut Checker) {
+        let super_class = match self.super_class.as_ref().map(|expr| expr.kind()) {
+            Some(Kind::Variable(name)) => Some(name),
+            _ => None,
+        };
+        checker.class(&self.name, super_class, &self.traits, &self.fields, &self.methods);
+    }
+
     fn kind(&self) -> StatementKind {
         StatementKind::ClassStatement
     }
 }
+
+pub struct TraitStatement {
+    pub(crate) name: Token,
+    pub(crate) methods: Vec<Rc<dyn Statement>>,
+    pub(crate) required: Vec<Function>,
+    pub(crate) constant: bool,
+}
+
+impl Statement for TraitStatement {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let method_environment = Rc::new(Environment::new_child(Rc::clone(&env)));
+        let mut methods = HashMap::new();
+        for method in &self.methods {
+            if let StatementKind::Function(function) = method.kind() {
+                if let Value::Function(callable) = function.evaluate(Rc::clone(&method_environment))? {
+                    methods.insert(function.name.lexeme.clone(), callable);
+                }
+            }
+        }
+        let value = Value::Trait(Rc::new(Trait {
+            name: self.name.lexeme.clone(),
+            methods,
+            required: self.required.iter().map(|function| function.name.lexeme.clone()).collect(),
+        }));
+        declare(&env, &self.name, value, self.constant, None)?;
+        Ok(Value::None)
+    }
+
+    fn check(&self, checker: &mut Checker) {
+        checker.trait_declaration(&self.name, &self.methods, &self.required);
+    }
+
+    fn kind(&self) -> StatementKind {
+        StatementKind::TraitStatement
+    }
+}
+
+pub struct EnumStatement {
+    pub(crate) name: Token,
+    pub(crate) variants: Vec<(Token, Vec<Token>)>,
+    pub(crate) constant: bool,
+}
+
+impl Statement for EnumStatement {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let variants = self
+            .variants
+            .iter()
+            .map(|(name, fields)| {
+                let fields = fields.iter().map(|field| field.lexeme.clone()).collect();
+                (name.lexeme.clone(), fields)
+            })
+            .collect();
+        let value = Value::Enum(Rc::new(Enum {
+            name: self.name.lexeme.clone(),
+            variants,
+        }));
+        declare(&env, &self.name, value, self.constant, None)?;
+        Ok(Value::None)
+    }
+
+    fn check(&self, checker: &mut Checker) {
+        checker.enum_declaration(&self.name);
+    }
+
+    fn kind(&self) -> StatementKind {
+        StatementKind::EnumStatement
+    }
+}
+
+pub struct RecordStatement {
+    pub(crate) name: Token,
+    pub(crate) parameters: Vec<Parameter>,
+    pub(crate) constant: bool,
+}
+
+impl Statement for RecordStatement {
+    fn evaluate(&self, env: Rc<Environment>) -> Result<Value, (String, Token)> {
+        let value = Value::RecordType(Rc::new(Record {
+            name: self.name.clone(),
+            parameters: self.parameters.clone(),
+            environment: Rc::clone(&env),
+        }));
+        declare(&env, &self.name, value, self.constant, None)?;
+        Ok(Value::None)
+    }
+
+    fn check(&self, checker: &mut Checker) {
+        checker.record(&self.name, &self.parameters);
+    }
+
+    fn kind(&self) -> StatementKind {
+        StatementKind::RecordStatement
+    }
+}
@@ -5,22 +5,50 @@ pub enum TokenType {
     RightParen,
     LeftBrace,
     RightBrace,
+    HashLeftBrace,
+    LeftBracket,
+    RightBracket,
     Comma,
     Dot,
+    DotDot,
+    DotDotEqual,
+    DotDotDot,
     Minus,
     Plus,
     SemiColon,
+    Colon,
+    Question,
+    QuestionQuestion,
+    QuestionDot,
     Slash,
     Star,
+    StarStar,
+    TildeSlash,
+    Percent,
+    Ampersand,
+    Pipe,
+    Caret,
+    Tilde,
 
     Bang,
     BangEqual,
     Equal,
     EqualEqual,
+    FatArrow,
+    Arrow,
     Greater,
     GreaterEqual,
     Less,
     LessEqual,
+    LessLess,
+    GreaterGreater,
+    PlusEqual,
+    MinusEqual,
+    StarEqual,
+    SlashEqual,
+    PercentEqual,
+    PlusPlus,
+    MinusMinus,
 
     Identifier,
     String,
@@ -28,20 +56,29 @@ pub enum TokenType {
 
     And,
     Class,
+    Const,
     Else,
+    Enum,
     False,
     Fun,
     For,
     If,
+    In,
+    Match,
     Nil,
+    Not,
+    NotIn,
     Or,
     Print,
+    Record,
     Return,
     Super,
     This,
+    Trait,
     True,
     Var,
     While,
+    Yield,
 
     EOF,
 }
@@ -0,0 +1,185 @@
+use crate::token::Token;
+use crate::value::{Class, Parameter, Value};
+use std::fmt;
+use std::rc::Rc;
+
+/// Typ z anotace (`var x: Num`) nebo odvozeny kontrolou typu.
+/// `Any` znamena, ze typ nezname, a proti nemu se nic nehlasi.
+#[derive(Debug, Clone)]
+pub enum Type {
+    Any,
+    Nil,
+    Bool,
+    Int,
+    Float,
+    Num,
+    String,
+    List,
+    Tuple,
+    Set,
+    Range,
+    Generator,
+    Function(Option<Rc<Signature>>),
+    Class(String),
+    Instance(String),
+    Optional(Box<Type>),
+}
+
+/// Anotace ze zdrojoveho kodu. Token slouzi k hlaseni chyb,
+/// napriklad kdyz trida z anotace neexistuje.
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    pub(crate) token: Token,
+    pub(crate) ty: Type,
+}
+
+/// Znama signatura funkce: parametry s typy z anotaci a typ navratove hodnoty.
+pub struct Signature {
+    pub(crate) name: String,
+    pub(crate) parameters: Vec<Parameter>,
+    pub(crate) types: Vec<Type>,
+    pub(crate) returns: Type,
+}
+
+impl fmt::Debug for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Signature")
+            .field("name", &self.name)
+            .field("types", &self.types)
+            .field("returns", &self.returns)
+            .finish()
+    }
+}
+
+impl Type {
+    pub fn from_name(name: &str) -> Type {
+        match name {
+            "Any" => Type::Any,
+            "Nil" => Type::Nil,
+            "Bool" => Type::Bool,
+            "Int" => Type::Int,
+            "Float" => Type::Float,
+            "Num" => Type::Num,
+            "String" => Type::String,
+            "List" => Type::List,
+            "Tuple" => Type::Tuple,
+            "Set" => Type::Set,
+            "Range" => Type::Range,
+            "Generator" => Type::Generator,
+            "Fun" => Type::Function(None),
+            _ => Type::Instance(name.to_string()),
+        }
+    }
+
+    /// Jestli hodnota za behu odpovida typu, pro `--strict-types`.
+    /// Stejne jako pri kontrole `moon check` projde `Int` i jako `Float`.
+    pub fn matches(&self, value: &Value) -> bool {
+        match (self, value) {
+            (Type::Any, _) => true,
+            (Type::Optional(_), Value::None) => true,
+            (Type::Optional(inner), value) => inner.matches(value),
+            (Type::Nil, Value::None) => true,
+            (Type::Bool, Value::Bool(_)) => true,
+            (Type::Int | Type::Float | Type::Num, Value::Int(_) | Value::BigInt(_)) => true,
+            (Type::Float | Type::Num, Value::Number(_)) => true,
+            (Type::String, Value::String(_)) => true,
+            (Type::List, Value::List(_)) => true,
+            (Type::Tuple, Value::Tuple(_)) => true,
+            (Type::Set, Value::Set(_)) => true,
+            (Type::Range, Value::Range(_)) => true,
+            (Type::Generator, Value::Generator(_)) => true,
+            (Type::Function(_), Value::Function(_) | Value::Class(_)) => true,
+            (Type::Class(name), Value::Class(class)) => class.name == *name,
+            (Type::Instance(name), Value::Instance(instance)) => has_ancestor(&instance.class, name),
+            (Type::Instance(name), Value::Variant(variant)) => variant.owner.name == *name,
+            (Type::Instance(name), Value::Record(value)) => value.record.name.lexeme == *name,
+            (Type::Function(_), Value::RecordType(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// Nazev typu hodnoty, jak se pise v anotacich.
+    pub fn describe(value: &Value) -> String {
+        match value {
+            Value::Int(_) | Value::BigInt(_) => String::from("Int"),
+            Value::Number(_) => String::from("Float"),
+            Value::String(_) => String::from("String"),
+            Value::Bool(_) => String::from("Bool"),
+            Value::None => String::from("Nil"),
+            Value::List(_) => String::from("List"),
+            Value::Tuple(_) => String::from("Tuple"),
+            Value::Set(_) => String::from("Set"),
+            Value::Range(_) => String::from("Range"),
+            Value::Generator(_) => String::from("Generator"),
+            Value::Function(_) => String::from("Fun"),
+            Value::Class(class) => format!("trida {}", class.name),
+            Value::Trait(t) => format!("trait {}", t.name),
+            Value::Enum(e) => format!("vycet {}", e.name),
+            Value::Variant(variant) => variant.owner.name.clone(),
+            Value::RecordType(record) => format!("zaznam {}", record.name.lexeme),
+            Value::Record(value) => value.record.name.lexeme.clone(),
+            Value::Instance(instance) => instance.class.name.clone(),
+            Value::Return(value) => Type::describe(value),
+        }
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Type::Int | Type::Float | Type::Num)
+    }
+
+    /// Typ bez `?`. Kontrola nezna zuzeni typu po `x != nil`, proto se
+    /// s `Num?` pri operacich zachazi jako s `Num`.
+    pub fn required(&self) -> &Type {
+        match self {
+            Type::Optional(inner) => inner,
+            other => other,
+        }
+    }
+}
+
+/// Anotace zna jen jmeno tridy, proto se tady predkove i traity hledaji
+/// podle jmena.
+fn has_ancestor(class: &Class, name: &str) -> bool {
+    if class.name == name || class.traits.iter().any(|used| used.name == name) {
+        return true;
+    }
+    match &class.super_class {
+        Some(super_class) => has_ancestor(super_class, name),
+        None => false,
+    }
+}
+
+/// Signatura je jen doplnujici informace, dve funkce jsou vzdy stejneho typu.
+impl PartialEq for Type {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Type::Function(_), Type::Function(_)) => true,
+            (Type::Class(a), Type::Class(b)) | (Type::Instance(a), Type::Instance(b)) => a == b,
+            (Type::Optional(a), Type::Optional(b)) => a == b,
+            (a, b) => std::mem::discriminant(a) == std::mem::discriminant(b),
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Any => write!(f, "Any"),
+            Type::Nil => write!(f, "Nil"),
+            Type::Bool => write!(f, "Bool"),
+            Type::Int => write!(f, "Int"),
+            Type::Float => write!(f, "Float"),
+            Type::Num => write!(f, "Num"),
+            Type::String => write!(f, "String"),
+            Type::List => write!(f, "List"),
+            Type::Tuple => write!(f, "Tuple"),
+            Type::Set => write!(f, "Set"),
+            Type::Range => write!(f, "Range"),
+            Type::Generator => write!(f, "Generator"),
+            Type::Function(_) => write!(f, "Fun"),
+            Type::Class(name) => write!(f, "trida {}", name),
+            Type::Instance(name) => write!(f, "{}", name),
+            Type::Optional(inner) => write!(f, "{}?", inner),
+        }
+    }
+}
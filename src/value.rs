@@ -1,38 +1,91 @@
+use crate::bigint::BigInt;
+use crate::expressions::Expr;
+use crate::enums::{Enum, VariantValue};
+use crate::records::{Record, RecordValue};
+use crate::sets::SetValue;
+use crate::generator::Generator;
+use crate::natives::native_function;
 use crate::token::Token;
+use crate::types::{Annotation, Type};
 use crate::moonenv::Environment;
 use std::borrow::Borrow;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 
 #[derive(Debug, Clone)]
 pub enum Value {
     String(String),
     Number(f64),
+    Int(i64),
+    BigInt(Rc<BigInt>),
     Bool(bool),
     None,
     Function(Rc<Callable>),
     Return(Box<Value>),
     Class(Rc<Class>),
+    Trait(Rc<Trait>),
     Instance(Rc<InstanceValue>),
+    Enum(Rc<Enum>),
+    Variant(Rc<VariantValue>),
+    RecordType(Rc<Record>),
+    Record(Rc<RecordValue>),
+    List(Rc<RefCell<Vec<Value>>>),
+    Tuple(Rc<Vec<Value>>),
+    Set(Rc<RefCell<SetValue>>),
+    Range(RangeValue),
+    Generator(Rc<Generator>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RangeValue {
+    pub(crate) start: Option<i64>,
+    pub(crate) end: Option<i64>,
+    pub(crate) inclusive: bool,
+    pub(crate) step: i64,
+}
+
+#[derive(Clone)]
+pub struct Parameter {
+    pub(crate) name: Token,
+    pub(crate) default: Option<Rc<dyn Expr>>,
+    pub(crate) rest: bool,
+    pub(crate) annotation: Option<Annotation>,
+}
+
+/// Pole deklarovane v tele tridy (`x: Num = 0;`). Inicializator se
+/// vyhodnoti pro kazdou novou instanci pred zavolanim `init`.
+#[derive(Clone)]
+pub struct Field {
+    pub(crate) name: Token,
+    pub(crate) annotation: Option<Annotation>,
+    pub(crate) init: Option<Rc<dyn Expr>>,
+}
+
+impl Debug for Field {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Field").field("name", &self.name.lexeme).finish()
+    }
 }
 
 pub struct Callable {
-    pub(crate) arity: usize,
+    pub(crate) parameters: Vec<Parameter>,
     pub(crate) function: Rc<dyn Fn(Vec<Value>, Rc<Environment>) -> Result<Value, (String, Token)>>,
     pub(crate) string: String,
     pub(crate) name: Token,
     pub(crate) environment: Rc<Environment>,
     pub(crate) is_initializer: RefCell<bool>,
+    pub(crate) return_type: Option<Annotation>,
 }
 
 impl Debug for Callable {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("Callable")
             .field("string", &self.string)
-            .field("arity", &self.arity)
+            .field("arity", &arity_description(&self.parameters))
             .field("name", &self.name)
             .finish()
     }
@@ -43,12 +96,13 @@ impl Clone for Callable {
         let borrow: &Environment = self.environment.borrow();
         let env_clone = Rc::new(borrow.clone());
         Callable {
-            arity: self.arity,
+            parameters: self.parameters.clone(),
             function: Rc::clone(&self.function),
             string: self.string.clone(),
             name: self.name.clone(),
             environment: env_clone,
             is_initializer: RefCell::new(*self.is_initializer.borrow()),
+            return_type: self.return_type.clone(),
         }
     }
 }
@@ -59,7 +113,23 @@ pub struct Class {
     pub(crate) arity: usize,
     pub(crate) methods: RefCell<HashMap<String, Value>>,
     pub(crate) super_class: Option<Rc<Class>>,
+    pub(crate) statics: HashMap<String, Rc<Callable>>,
+    pub(crate) getters: HashMap<String, Rc<Callable>>,
+    pub(crate) setters: HashMap<String, Rc<Callable>>,
+    pub(crate) fields: Vec<Field>,
+    pub(crate) traits: Vec<Rc<Trait>>,
+    pub(crate) environment: Rc<Environment>,
 }
+
+/// Trait z `trait Jmeno { ... }`. Vychozi metody se pri vytvoreni tridy
+/// zkopiruji do jejich metod, povinne metody musi trida definovat sama.
+#[derive(Debug)]
+pub struct Trait {
+    pub(crate) name: String,
+    pub(crate) methods: HashMap<String, Rc<Callable>>,
+    pub(crate) required: Vec<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct InstanceValue {
     pub(crate) class: Rc<Class>,
@@ -67,16 +137,21 @@ pub struct InstanceValue {
 }
 
 impl InstanceValue {
-    pub fn get_value(&self, name: &Token) -> Result<Value, (String, Token)> {
+    pub fn get_value(self: &Rc<Self>, name: &Token) -> Result<Value, (String, Token)> {
         match self.class.find_method(name.clone().lexeme) {
             None => {},
             Some(callable) => {
                 let updated_method = callable.clone();
-                updated_method.bind(Value::Instance(Rc::new(self.clone())));
+                updated_method.bind(Value::Instance(Rc::clone(self)));
                 return Ok(Value::Function(updated_method));
             }
         }
 
+        if let Some(getter) = self.class.find_accessor(&name.lexeme, false) {
+            getter.bind(Value::Instance(Rc::clone(self)));
+            return getter.call(Vec::new(), Vec::new());
+        }
+
         match self.fields.borrow_mut().get(&*name.lexeme) {
             None => Err((
                     format!("Undefined property '{}'.", name.lexeme),
@@ -86,8 +161,141 @@ impl InstanceValue {
         }
     }
 
-    pub fn set_value(&self, name: String, value: Value) {
-        self.fields.borrow_mut().insert(name, value);
+    pub fn set_value(self: &Rc<Self>, name: &Token, value: Value) -> Result<(), (String, Token)> {
+        self.class.check_field(name, &value)?;
+        if let Some(setter) = self.class.find_accessor(&name.lexeme, true) {
+            setter.bind(Value::Instance(Rc::clone(self)));
+            setter.call(vec![value], Vec::new())?;
+            return Ok(());
+        }
+        if self.class.find_accessor(&name.lexeme, false).is_some() {
+            return Err((
+                format!("Vlastnost '{}' jde jen cist.", name.lexeme),
+                name.clone(),
+            ));
+        }
+        self.fields.borrow_mut().insert(name.lexeme.clone(), value);
+        Ok(())
+    }
+
+    /// Zavola specialni metodu instance (`__add__`, `__str__`, ...).
+    /// `None` znamena, ze ji trida nedefinuje a plati vychozi chovani.
+    pub(crate) fn call_special(
+        self: &Rc<Self>,
+        name: &str,
+        arguments: Vec<Value>,
+    ) -> Option<Result<Value, (String, Token)>> {
+        let method = self.class.find_method(name.to_string())?;
+        method.bind(Value::Instance(Rc::clone(self)));
+        if let Err(msg) = method.check_arguments(arguments.len(), &[]) {
+            return Some(Err((msg, method.name.clone())));
+        }
+        Some(method.call(arguments, Vec::new()))
+    }
+
+    /// Text instance pro `print`. Na rozdil od `Display` chybu z `__str__`
+    /// neztrati a trva na tom, aby `__str__` vratila retezec.
+    fn to_text(self: &Rc<Self>) -> Result<String, (String, Token)> {
+        match self.call_special("__str__", Vec::new()) {
+            Some(Ok(Value::String(text))) => Ok(text),
+            Some(Ok(other)) => {
+                let method = self.class.find_method(String::from("__str__")).unwrap();
+                Err((
+                    format!("Metoda __str__ musi vratit retezec, ne {}.", Type::describe(&other)),
+                    method.name.clone(),
+                ))
+            }
+            Some(Err(err)) => Err(err),
+            None => Ok(format!("{} instance", self.class.name)),
+        }
+    }
+}
+
+impl RangeValue {
+    pub fn contains(&self, value: &Value) -> bool {
+        let number = match value {
+            Value::Int(a) => *a as i128,
+            Value::Number(a) if a.fract() == 0.0 && a.abs() < i64::MAX as f64 => *a as i128,
+            _ => return false,
+        };
+        let start = self.start.map(|a| a as i128);
+        let end = self.end.map(|a| a as i128);
+        let step = self.step as i128;
+        let after_start = match start {
+            None => true,
+            Some(start) if step > 0 => number >= start,
+            Some(start) => number <= start,
+        };
+        let before_end = match end {
+            None => true,
+            Some(end) if step > 0 && self.inclusive => number <= end,
+            Some(end) if step > 0 => number < end,
+            Some(end) if self.inclusive => number >= end,
+            Some(end) => number > end,
+        };
+        let on_step = match start {
+            None => step == 1 || step == -1,
+            Some(start) => (number - start) % step == 0,
+        };
+        after_start && before_end && on_step
+    }
+
+    /// Prevede rozsah na hranice rezu pro posloupnost dane delky,
+    /// zaporne hodnoty se pocitaji od konce.
+    pub fn slice_indices(&self, length: usize) -> Result<Vec<usize>, String> {
+        if self.step <= 0 {
+            return Err(String::from("Krok rezu musi byt kladny."));
+        }
+        let length = length as i64;
+        let resolve = |index: i64| -> i64 {
+            let index = if index < 0 { index + length } else { index };
+            index.clamp(0, length)
+        };
+        let start = resolve(self.start.unwrap_or(0));
+        let end = match self.end {
+            None => length,
+            Some(end) if self.inclusive => resolve(end).saturating_add(1).min(length),
+            Some(end) => resolve(end),
+        };
+        Ok((start..end.max(start))
+            .step_by(self.step as usize)
+            .map(|index| index as usize)
+            .collect())
+    }
+
+    pub fn get_property(&self, name: &Token) -> Result<Value, (String, Token)> {
+        let optional = |value: Option<i64>| value.map(Value::Int).unwrap_or(Value::None);
+        match &*name.lexeme {
+            "start" => Ok(optional(self.start)),
+            "end" => Ok(optional(self.end)),
+            "step" => Ok(Value::Int(self.step)),
+            "contains" => {
+                let range = *self;
+                Ok(native_function("contains", &["value"], move |arguments| {
+                    Ok(Value::Bool(range.contains(&arguments[0])))
+                }))
+            }
+            _ => Err((
+                format!("Rozsah nema vlastnost '{}'.", name.lexeme),
+                name.clone(),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for RangeValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(start) = self.start {
+            write!(f, "{}", start)?;
+        }
+        write!(f, "{}", if self.inclusive { "..=" } else { ".." })?;
+        if let Some(end) = self.end {
+            write!(f, "{}", end)?;
+        }
+        if self.step != 1 {
+            write!(f, " step {}", self.step)?;
+        }
+        Ok(())
     }
 }
 
@@ -98,29 +306,100 @@ impl Clone for Class {
             arity: self.arity,
             methods: RefCell::clone(&self.methods),
             super_class: self.super_class.clone(),
+            statics: self.statics.clone(),
+            getters: self.getters.clone(),
+            setters: self.setters.clone(),
+            fields: self.fields.clone(),
+            traits: self.traits.clone(),
+            environment: Rc::clone(&self.environment),
         }
     }
 }
 
 impl Class {
-    pub(crate) fn call(&self, arguments: Vec<Value>) -> Result<Value, (String, Token)> {
+    pub(crate) fn call(
+        self: &Rc<Self>,
+        arguments: Vec<Value>,
+        named: Vec<(Token, Value)>,
+    ) -> Result<Value, (String, Token)> {
         let instance = Rc::new(InstanceValue {
-            class: Rc::new(self.clone()),
+            class: Rc::clone(self),
             fields: RefCell::new(HashMap::new()),
         });
-        match self.methods.borrow().get("init") {
-            Some(a) => match a {
-                Value::Function(callable) => {
-                    callable.bind(Value::Instance(Rc::clone(&instance)));
-                    return callable.call(arguments);
-                }
-                _ => {},
-            }
-            _ => {},
+        self.initialize_fields(&instance)?;
+        if let Some(callable) = self.initializer() {
+            callable.bind(Value::Instance(Rc::clone(&instance)));
+            return callable.call(arguments, named);
         }
         Ok(Value::Instance(instance))
     }
 
+    /// Nastavi instanci pole deklarovana v tele tridy, nejdriv ta zdedena.
+    fn initialize_fields(&self, instance: &Rc<InstanceValue>) -> Result<(), (String, Token)> {
+        if let Some(super_class) = &self.super_class {
+            super_class.initialize_fields(instance)?;
+        }
+        if self.fields.is_empty() {
+            return Ok(());
+        }
+        let environment = Rc::new(Environment::new_child(Rc::clone(&self.environment)));
+        environment.define(String::from("this"), Value::Instance(Rc::clone(instance)));
+        for field in &self.fields {
+            let value = match &field.init {
+                Some(init) => {
+                    let value = init.evaluate(Rc::clone(&environment))?;
+                    self.check_field(&field.name, &value)?;
+                    value
+                }
+                None => Value::None,
+            };
+            instance.fields.borrow_mut().insert(field.name.lexeme.clone(), value);
+        }
+        Ok(())
+    }
+
+    /// Pro `--strict-types`: hodnota pole musi odpovidat anotaci z tela tridy.
+    fn check_field(&self, name: &Token, value: &Value) -> Result<(), (String, Token)> {
+        if !self.environment.strict_types {
+            return Ok(());
+        }
+        match self.find_field(&name.lexeme).and_then(|field| field.annotation.as_ref()) {
+            Some(annotation) if !annotation.ty.matches(value) => Err((
+                format!(
+                    "Pole '{}' ma typ {}, ale hodnota je {}.",
+                    name.lexeme,
+                    annotation.ty,
+                    Type::describe(value)
+                ),
+                name.clone(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn find_field(&self, name: &str) -> Option<&Field> {
+        match self.fields.iter().find(|field| field.name.lexeme == name) {
+            Some(field) => Some(field),
+            None => self.super_class.as_ref()?.find_field(name),
+        }
+    }
+
+    /// Konstruktor tridy, pripadne zdedeny z nadtridy.
+    pub(crate) fn initializer(&self) -> Option<Rc<Callable>> {
+        self.find_method(String::from("init"))
+    }
+
+    pub(crate) fn check_arguments(&self, arguments: usize, named: &[Token]) -> Result<(), String> {
+        match self.initializer() {
+            Some(callable) => callable.check_arguments(arguments, named),
+            None if arguments > 0 || !named.is_empty() => Err(format!(
+                "Ocekavano 0 argumentu ale bylo zadano {}.",
+                arguments + named.len()
+            )),
+            None => Ok(()),
+        }
+    }
+
     pub(crate) fn find_method(&self, name: String) -> Option<Rc<Callable>> {
         match self.methods.borrow().get(&*name) {
             None => match &self.super_class {
@@ -133,20 +412,102 @@ impl Class {
             },
         }
     }
+
+    pub(crate) fn find_static(&self, name: &str) -> Option<Rc<Callable>> {
+        match self.statics.get(name) {
+            Some(callable) => Some(Rc::clone(callable)),
+            None => self.super_class.as_ref()?.find_static(name),
+        }
+    }
+
+    /// Najde getter (nebo setter) vlastnosti, i ve zdedenych tridach.
+    pub(crate) fn find_accessor(&self, name: &str, setter: bool) -> Option<Rc<Callable>> {
+        let accessors = if setter { &self.setters } else { &self.getters };
+        match accessors.get(name) {
+            Some(callable) => Some(Rc::clone(callable)),
+            None => self.super_class.as_ref()?.find_accessor(name, setter),
+        }
+    }
+
+    pub fn get_static(&self, name: &Token) -> Result<Value, (String, Token)> {
+        match self.find_static(&name.lexeme) {
+            Some(callable) => Ok(Value::Function(callable)),
+            None => Err((
+                format!("Trida {} nema statickou metodu '{}'.", self.name, name.lexeme),
+                name.clone(),
+            )),
+        }
+    }
+}
+
+pub(crate) fn min_arity(parameters: &[Parameter]) -> usize {
+    parameters
+        .iter()
+        .filter(|parameter| parameter.default.is_none() && !parameter.rest)
+        .count()
+}
+
+fn max_arity(parameters: &[Parameter]) -> Option<usize> {
+    if parameters.iter().any(|parameter| parameter.rest) {
+        None
+    } else {
+        Some(parameters.len())
+    }
+}
+
+pub(crate) fn arity_description(parameters: &[Parameter]) -> String {
+    match (min_arity(parameters), max_arity(parameters)) {
+        (min, Some(max)) if min == max => format!("{}", min),
+        (min, Some(max)) => format!("{} az {}", min, max),
+        (min, None) => format!("alespon {}", min),
+    }
+}
+
+/// Zkontroluje pocet a jmena argumentu proti parametrum. Pouziva ji volani
+/// za behu i kontrola typu, ktera zna signaturu funkce predem.
+pub(crate) fn check_arguments(parameters: &[Parameter], arguments: usize, named: &[Token]) -> Result<(), String> {
+    let arity_error = format!(
+        "Ocekavano {} argumentu ale bylo zadano {}.",
+        arity_description(parameters),
+        arguments + named.len()
+    );
+    let positional = parameters.iter().filter(|parameter| !parameter.rest).count();
+    if arguments > positional && max_arity(parameters).is_some() {
+        return Err(arity_error);
+    }
+    let mut filled: Vec<bool> = (0..positional).map(|i| i < arguments).collect();
+    for name in named {
+        let index = parameters[..positional]
+            .iter()
+            .position(|parameter| parameter.name.lexeme == name.lexeme);
+        match index {
+            Some(index) if filled[index] => {
+                return Err(format!("Parametr '{}' byl zadan vicekrat.", name.lexeme));
+            }
+            Some(index) => filled[index] = true,
+            None => return Err(format!("Neznamy parametr '{}'.", name.lexeme)),
+        }
+    }
+    let missing = filled
+        .iter()
+        .zip(parameters)
+        .any(|(filled, parameter)| !filled && parameter.default.is_none());
+    if missing {
+        return Err(arity_error);
+    }
+    Ok(())
 }
 
 impl Callable {
-    pub(crate) fn call(&self, arguments: Vec<Value>) -> Result<Value, (String, Token)> {
-        if self.arity != arguments.len() {
-            return Err((
-                    format!(
-                        "Expected {} arguments but got {}.",
-                        self.arity,
-                        arguments.len()
-                    ),
-                    self.name.clone(),
-            ));
-        };
+    pub(crate) fn call(
+        &self,
+        arguments: Vec<Value>,
+        named: Vec<(Token, Value)>,
+    ) -> Result<Value, (String, Token)> {
+        let (arguments, defaulted) = self.bind_arguments(arguments, named)?;
+        if self.environment.strict_types {
+            self.check_parameters(&arguments, &defaulted)?;
+        }
 
         self.environment.define(
             self.name.lexeme.clone(),
@@ -160,11 +521,106 @@ impl Callable {
                 Ok(a) => Ok(a),
                 Err(msg) => Err((msg, self.name.clone())),
             }
+        } else if self.environment.strict_types {
+            self.check_return(result?)
         } else {
             result 
         }
     }
 
+    /// Pro `--strict-types`: zkontroluje navazane argumenty proti anotacim parametru.
+    /// Spatnou vychozi hodnotu ohlasi u parametru, ne u volani.
+    fn check_parameters(&self, arguments: &[Value], defaulted: &[bool]) -> Result<(), (String, Token)> {
+        let parameters = self.parameters.iter().zip(arguments).zip(defaulted);
+        for ((parameter, argument), defaulted) in parameters {
+            let annotation = match &parameter.annotation {
+                Some(annotation) if !annotation.ty.matches(argument) => annotation,
+                _ => continue,
+            };
+            let (what, token) = if *defaulted {
+                ("vychozi hodnota", parameter.name.clone())
+            } else {
+                ("argument", self.name.clone())
+            };
+            return Err((
+                format!(
+                    "Parametr '{}' funkce '{}' ma typ {}, ale {} je {}.",
+                    parameter.name.lexeme,
+                    self.name.lexeme,
+                    annotation.ty,
+                    what,
+                    Type::describe(argument)
+                ),
+                token,
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_return(&self, value: Value) -> Result<Value, (String, Token)> {
+        match &self.return_type {
+            Some(annotation) if !annotation.ty.matches(&value) => Err((
+                format!(
+                    "Funkce '{}' ma vracet {}, ale vraci {}.",
+                    self.name.lexeme,
+                    annotation.ty,
+                    Type::describe(&value)
+                ),
+                self.name.clone(),
+            )),
+            _ => Ok(value),
+        }
+    }
+
+    pub(crate) fn check_arguments(&self, arguments: usize, named: &[Token]) -> Result<(), String> {
+        check_arguments(&self.parameters, arguments, named)
+    }
+
+    fn bind_arguments(
+        &self,
+        arguments: Vec<Value>,
+        named: Vec<(Token, Value)>,
+    ) -> Result<(Vec<Value>, Vec<bool>), (String, Token)> {
+        let names: Vec<Token> = named.iter().map(|(name, _)| name.clone()).collect();
+        if let Err(msg) = self.check_arguments(arguments.len(), &names) {
+            return Err((msg, self.name.clone()));
+        }
+
+        let mut slots: Vec<Option<Value>> = vec![None; self.parameters.len()];
+        let mut rest: Vec<Value> = Vec::new();
+        let mut arguments = arguments.into_iter();
+        for (slot, parameter) in slots.iter_mut().zip(&self.parameters) {
+            if parameter.rest {
+                rest.extend(arguments.by_ref());
+            } else if let Some(argument) = arguments.next() {
+                *slot = Some(argument);
+            }
+        }
+        for (name, value) in named {
+            if let Some(index) = self.parameters.iter().position(|p| p.name.lexeme == name.lexeme) {
+                slots[index] = Some(value);
+            }
+        }
+
+        let mut bound = Vec::with_capacity(slots.len());
+        let defaulted = slots
+            .iter()
+            .zip(&self.parameters)
+            .map(|(slot, parameter)| slot.is_none() && parameter.default.is_some())
+            .collect();
+        for (slot, parameter) in slots.into_iter().zip(&self.parameters) {
+            let value = match (slot, &parameter.default) {
+                (Some(value), _) => value,
+                (None, _) if parameter.rest => Value::List(Rc::new(RefCell::new(rest.clone()))),
+                (None, Some(default)) => default.evaluate(Rc::clone(&self.environment))?,
+                (None, None) => Value::None,
+            };
+            self.environment.define(parameter.name.lexeme.clone(), value.clone());
+            bound.push(value);
+        }
+        Ok((bound, defaulted))
+    }
+
     pub(crate) fn bind(&self, instance: Value) {
         self.environment.define(String::from("this"), instance);
     }
@@ -183,27 +639,221 @@ impl PartialEq for Value {
         match (self, other) {
             (Value::String(a), Value::String(b)) => a == b,
             (Value::Number(a), Value::Number(b)) => a == b,
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::BigInt(a), Value::BigInt(b)) => a == b,
+            (Value::Int(_), Value::BigInt(_)) | (Value::BigInt(_), Value::Int(_)) => false,
+            (Value::Int(_) | Value::BigInt(_), Value::Number(b)) => integer_equals_float(self, *b),
+            (Value::Number(a), Value::Int(_) | Value::BigInt(_)) => integer_equals_float(other, *a),
             (Value::None, Value::None) => true,
             (Value::Bool(a), Value::Bool(b)) => a == b,
             (Value::Function(a), Value::Function(b)) => Rc::ptr_eq(a, b),
+            (Value::List(a), Value::List(b)) => Rc::ptr_eq(a, b) || *(**a).borrow() == *(**b).borrow(),
+            (Value::Tuple(a), Value::Tuple(b)) => a == b,
+            (Value::Set(a), Value::Set(b)) => Rc::ptr_eq(a, b) || *(**a).borrow() == *(**b).borrow(),
+            (Value::Range(a), Value::Range(b)) => a == b,
+            (Value::Generator(a), Value::Generator(b)) => Rc::ptr_eq(a, b),
+            (Value::Class(a), Value::Class(b)) => Rc::ptr_eq(a, b),
+            (Value::Trait(a), Value::Trait(b)) => Rc::ptr_eq(a, b),
+            (Value::Enum(a), Value::Enum(b)) => Rc::ptr_eq(a, b),
+            (Value::Variant(a), Value::Variant(b)) => a == b,
+            (Value::RecordType(a), Value::RecordType(b)) => Rc::ptr_eq(a, b),
+            (Value::Record(a), Value::Record(b)) => a == b,
+            // `__eq__` muze selhat, vola ho jen `Value::equals`.
+            (Value::Instance(a), Value::Instance(b)) => Rc::ptr_eq(a, b),
             _ => false,
         }
     }
 }
 
+/// Cele cislo se rovna desetinnemu, jen kdyz je desetinne cislo cele a obe
+/// hodnoty jsou presne stejne. Prevod na f64 by u velkych cisel zaokrouhlil.
+fn integer_equals_float(integer: &Value, float: f64) -> bool {
+    if float.fract() != 0.0 {
+        return false;
+    }
+    match BigInt::from_f64(float) {
+        Some(number) => Value::integer(number) == *integer,
+        None => false,
+    }
+}
+
 impl Eq for Value {}
 
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Value::Number(_) | Value::Int(_) | Value::BigInt(_) => {
+                let number = self.as_float().unwrap();
+                let number = if number == 0.0 { 0.0 } else { number };
+                number.to_bits().hash(state);
+            }
+            Value::String(a) => a.hash(state),
+            Value::Bool(a) => a.hash(state),
+            Value::None => 0.hash(state),
+            Value::Function(a) => Rc::as_ptr(a).hash(state),
+            Value::Return(a) => a.hash(state),
+            Value::Class(a) => Rc::as_ptr(a).hash(state),
+            Value::Trait(a) => Rc::as_ptr(a).hash(state),
+            Value::Enum(a) => Rc::as_ptr(a).hash(state),
+            Value::Variant(a) => {
+                Rc::as_ptr(&a.owner).hash(state);
+                a.index.hash(state);
+                a.values.hash(state);
+            }
+            Value::RecordType(a) => Rc::as_ptr(a).hash(state),
+            Value::Record(a) => {
+                Rc::as_ptr(&a.record).hash(state);
+                a.values.hash(state);
+            }
+            Value::Instance(a) => match a.call_special("__hash__", Vec::new()) {
+                Some(Ok(result)) => result.hash(state),
+                _ => Rc::as_ptr(a).hash(state),
+            },
+            Value::List(a) => (**a).borrow().hash(state),
+            Value::Tuple(a) => a.hash(state),
+            // Na poradi prvku nezalezi, staci delka.
+            Value::Set(a) => (**a).borrow().items().len().hash(state),
+            Value::Range(a) => a.hash(state),
+            Value::Generator(a) => Rc::as_ptr(a).hash(state),
+        }
+    }
+}
+
+impl Value {
+    pub(crate) fn integer(number: BigInt) -> Value {
+        match number.to_i64() {
+            Some(a) => Value::Int(a),
+            None => Value::BigInt(Rc::new(number)),
+        }
+    }
+
+    /// Jako `Display`, ale chyby ze `__str__` vnorenych instanci vrati.
+    pub(crate) fn to_text(&self) -> Result<String, (String, Token)> {
+        let join = |items: &[Value]| -> Result<String, (String, Token)> {
+            let texts = items.iter().map(Value::to_text).collect::<Result<Vec<_>, _>>()?;
+            Ok(texts.join(", "))
+        };
+        match self {
+            Value::Instance(a) => a.to_text(),
+            Value::List(a) => Ok(format!("[{}]", join(&(**a).borrow())?)),
+            Value::Tuple(a) if a.len() == 1 => Ok(format!("({},)", join(a)?)),
+            Value::Tuple(a) => Ok(format!("({})", join(a)?)),
+            Value::Set(a) => Ok(format!("#{{{}}}", join((**a).borrow().items())?)),
+            Value::Variant(a) if a.values.is_empty() => Ok(a.to_string()),
+            Value::Variant(a) => Ok(format!("{}.{}({})", a.owner.name, a.name(), join(&a.values)?)),
+            Value::Record(a) => {
+                let mut fields = Vec::new();
+                for (field, value) in a.record.field_names().zip(&a.values) {
+                    fields.push(format!("{}: {}", field, value.to_text()?));
+                }
+                Ok(format!("{}({})", a.record.name.lexeme, fields.join(", ")))
+            }
+            _ => Ok(self.to_string()),
+        }
+    }
+
+    /// Jako `==`, ale chyby z `__eq__` vnorenych instanci vrati.
+    pub(crate) fn equals(&self, other: &Value) -> Result<bool, (String, Token)> {
+        let all = |a: &[Value], b: &[Value]| -> Result<bool, (String, Token)> {
+            if a.len() != b.len() {
+                return Ok(false);
+            }
+            for (a, b) in a.iter().zip(b) {
+                if !a.equals(b)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        };
+        match (self, other) {
+            (Value::Instance(a), Value::Instance(_)) => match a.call_special("__eq__", vec![other.clone()]) {
+                Some(result) => Ok(!matches!(result?, Value::Bool(false) | Value::None)),
+                None => Ok(self == other),
+            },
+            (Value::List(a), Value::List(b)) => Ok(Rc::ptr_eq(a, b) || all(&(**a).borrow(), &(**b).borrow())?),
+            (Value::Tuple(a), Value::Tuple(b)) => all(a, b),
+            (Value::Set(a), Value::Set(b)) => Ok(Rc::ptr_eq(a, b) || (**a).borrow().equals(&(**b).borrow())?),
+            (Value::Variant(a), Value::Variant(b)) => {
+                Ok(Rc::ptr_eq(&a.owner, &b.owner) && a.index == b.index && all(&a.values, &b.values)?)
+            }
+            (Value::Record(a), Value::Record(b)) => Ok(Rc::ptr_eq(&a.record, &b.record) && all(&a.values, &b.values)?),
+            _ => Ok(self == other),
+        }
+    }
+
+    /// Zavola `__hash__` vsech vnorenych instanci, aby se jejich chyba
+    /// ukazala driv, nez hodnotu zahashuje mnozina.
+    pub(crate) fn check_hash(&self) -> Result<(), (String, Token)> {
+        match self {
+            Value::Instance(a) => match a.call_special("__hash__", Vec::new()) {
+                Some(result) => result?.check_hash(),
+                None => Ok(()),
+            },
+            Value::Tuple(a) => a.iter().try_for_each(Value::check_hash),
+            Value::Variant(a) => a.values.iter().try_for_each(Value::check_hash),
+            Value::Record(a) => a.values.iter().try_for_each(Value::check_hash),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn as_float(&self) -> Option<f64> {
+        match self {
+            Value::Number(a) => Some(*a),
+            Value::Int(a) => Some(*a as f64),
+            Value::BigInt(a) => Some(a.to_f64()),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Value::String(a) => write!(f, "\"{}\"", a),
             Value::Number(a) => write!(f, "{}", a),
+            Value::Int(a) => write!(f, "{}", a),
+            Value::BigInt(a) => write!(f, "{}", a),
             Value::Bool(a) => write!(f, "{}", a),
             Value::None => write!(f, "nil"),
             Value::Function(a) => write!(f, "{}", a.string),
             Value::Return(a) => write!(f, "<return {}>", a),
             Value::Class(a) => write!(f, "{}", a.name),
-            Value::Instance(a) => write!(f, "{} instance", a.class.name),
+            Value::Trait(a) => write!(f, "{}", a.name),
+            Value::Enum(a) => write!(f, "{}", a.name),
+            Value::Variant(a) => write!(f, "{}", a),
+            Value::RecordType(a) => write!(f, "{}", a.name.lexeme),
+            Value::Record(a) => write!(f, "{}", a),
+            Value::Instance(a) => match a.call_special("__str__", Vec::new()) {
+                Some(Ok(Value::String(text))) => write!(f, "{}", text),
+                Some(Ok(other)) => write!(f, "{}", other),
+                _ => write!(f, "{} instance", a.class.name),
+            },
+            Value::List(a) => {
+                write!(f, "[")?;
+                for (i, item) in (**a).borrow().iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            Value::Tuple(a) => {
+                write!(f, "(")?;
+                for (i, item) in a.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                if a.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            Value::Set(a) => write!(f, "{}", (**a).borrow()),
+            Value::Range(a) => write!(f, "{}", a),
+            Value::Generator(a) => write!(f, "<generator {}>", a.name),
         }
     }
 }
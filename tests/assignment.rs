@@ -0,0 +1,48 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn compound_assignment_evaluates_the_target_once() {
+    let source = concat!(
+        "class Counter {\n",
+        "    n = 0;\n",
+        "}\n",
+        "var calls = Counter();\n",
+        "var b = Counter();\n",
+        "fun box() {\n",
+        "    var _c = calls.n = calls.n + 1;\n",
+        "    return b;\n",
+        "}\n",
+        "fun index() {\n",
+        "    var _c = calls.n = calls.n + 1;\n",
+        "    return 1;\n",
+        "}\n",
+        "var x = box().n += 5;\n",
+        "var xs = [1, 2, 3];\n",
+        "var y = xs[index()] *= 10;\n",
+        "print b.n;\n",
+        "print xs;\n",
+        "print calls.n;\n",
+    );
+    let result = run(source, &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.lines(), ["5", "[1, 20, 3]", "2"]);
+}
+
+#[test]
+fn increments_return_the_old_or_new_value() {
+    let source = concat!(
+        "var i = 1;\n",
+        "var a = i += 4;\n",
+        "var b = i -= 1;\n",
+        "var c = i *= 3;\n",
+        "var d = i %= 5;\n",
+        "var post = i++;\n",
+        "var pre = ++i;\n",
+        "var down = i--;\n",
+        "print [a, b, c, d, post, pre, down, i];\n",
+    );
+    let result = run(source, &[]);
+    assert_eq!(result.lines(), ["[5, 4, 12, 2, 2, 4, 4, 3]"]);
+}
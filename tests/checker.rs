@@ -0,0 +1,22 @@
+mod common;
+
+use common::check;
+
+#[test]
+fn integer_powers_are_integers() {
+    let result = check("var x: Int = 2 ** 3;\nvar y: Int = -(2 ** 70);\n");
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.code, Some(0));
+    let result = check("var z: Int = 2 ** -1;\n");
+    assert!(result.stderr.contains("Float"), "{}", result.stderr);
+}
+
+#[test]
+fn overloaded_operators_accept_unknown_operands() {
+    let source = "class V {\n    init(x) {\n        this.x = x;\n    }\n    __lt__(other) {\n        return this.x < other.x;\n    }\n    __add__(other) {\n        return V(this.x + other.x);\n    }\n}\nfun g(p) {\n    return p < V(1);\n}\nfun h(p) {\n    return p + V(1);\n}\nvar b: Bool = g(V(0));\n";
+    let result = check(source);
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.code, Some(0));
+    let result = check("var x = [1] < 2;\n");
+    assert!(result.stderr.contains("Lze porovnat jen 2 cisla."), "{}", result.stderr);
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn field_initializers_run_for_each_instance() {
+    let source = concat!(
+        "class Obdelnik {\n",
+        "    sirka = 1;\n",
+        "    vyska = 2;\n",
+        "    body = [0];\n",
+        "    static ctverec() {\n",
+        "        return Obdelnik();\n",
+        "    }\n",
+        "    get obsah() {\n",
+        "        return this.sirka * this.vyska;\n",
+        "    }\n",
+        "    set strana(v) {\n",
+        "        var _s = this.sirka = this.vyska = v;\n",
+        "    }\n",
+        "}\n",
+        "var a = Obdelnik();\n",
+        "var b = Obdelnik.ctverec();\n",
+        "print a.obsah;\n",
+        "var _x = a.strana = 3;\n",
+        "print a.obsah;\n",
+        "var _y = a.body[0] = 5;\n",
+        "print b.body;\n",
+        "class Pocitadlo {\n",
+        "    n = 10;\n",
+        "    init() {\n",
+        "        var _n = this.n = this.n + 1;\n",
+        "    }\n",
+        "}\n",
+        "print Pocitadlo().n;\n",
+    );
+    let result = run(source, &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.lines(), ["2", "9", "[0]", "11"]);
+}
+
+#[test]
+fn getters_without_setters_are_read_only() {
+    let source = "class A {\n    get b() {\n        return 1;\n    }\n}\nvar a = A();\nprint a.b;\na.b = 2;\n";
+    let result = run(source, &[]);
+    assert_eq!(result.lines(), ["1"]);
+    assert!(result.stderr.contains("Vlastnost 'b' jde jen cist."), "{}", result.stderr);
+    assert_eq!(result.code, Some(70));
+}
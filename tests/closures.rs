@@ -0,0 +1,40 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn closures_from_the_same_factory_are_independent() {
+    let result = run(
+        "fun counter() {\n\
+         \x20   var n = 0;\n\
+         \x20   return () => n = n + 1;\n\
+         }\n\
+         var k = counter();\n\
+         var a = k();\n\
+         var b = k();\n\
+         var c = k();\n\
+         var k2 = counter();\n\
+         print k();\n\
+         print k2();\n",
+        &[],
+    );
+    assert_eq!(result.lines(), ["4", "1"]);
+}
+
+#[test]
+fn named_and_lambda_closures_agree() {
+    let result = run(
+        "fun outer() {\n\
+         \x20   var n = 0;\n\
+         \x20   fun inc() { return n = n + 1; }\n\
+         \x20   return inc;\n\
+         }\n\
+         var j = outer();\n\
+         var a = j();\n\
+         var j2 = outer();\n\
+         print j();\n\
+         print j2();\n",
+        &[],
+    );
+    assert_eq!(result.lines(), ["2", "1"]);
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn empty_parentheses_are_an_empty_tuple() {
+    let result = run("var e = ();\nprint e;\nprint type(e);\nprint len(e);\nprint e == ();\n", &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.lines(), ["()", "\"Tuple\"", "0", "true"]);
+}
+
+#[test]
+fn sets_support_algebra_and_hash_tuples() {
+    let source = concat!(
+        "var a = #{1, 2, 3};\n",
+        "var b = set([3, 4, 3]);\n",
+        "print a | b;\n",
+        "print a & b;\n",
+        "print a - b;\n",
+        "print a.union(b) == b | a;\n",
+        "print len(b);\n",
+        "print set();\n",
+        "var t = (1, \"a\");\n",
+        "print #{t, (1, \"a\"), (1,)};\n",
+        "print t[1];\n",
+        "var (x, y) = t;\n",
+        "print x;\n",
+        "print (1, [2]) in #{(1, 2)};\n",
+    );
+    let result = run(source, &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(
+        result.lines(),
+        [
+            "#{1, 2, 3, 4}",
+            "#{3}",
+            "#{1, 2}",
+            "true",
+            "2",
+            "#{}",
+            "#{(1, \"a\"), (1,)}",
+            "\"a\"",
+            "1",
+            "false",
+        ]
+    );
+}
+
+#[test]
+fn unhashable_values_cannot_go_into_sets() {
+    let result = run("var s = #{(1, [2])};\n", &[]);
+    assert!(result.stderr.contains("nelze vlozit do mnoziny"), "{}", result.stderr);
+    assert_eq!(result.code, Some(70));
+}
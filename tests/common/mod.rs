@@ -0,0 +1,83 @@
+//! Pomocne funkce pro testy: skript se zapise do docasneho souboru
+//! a spusti se na nem prelozeny `moon`.
+#![allow(dead_code)]
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub struct Run {
+    pub stdout: String,
+    pub stderr: String,
+    pub code: Option<i32>,
+}
+
+impl Run {
+    fn from(output: Output) -> Run {
+        Run {
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            code: output.status.code(),
+        }
+    }
+
+    /// Vypsane radky, bez promptu REPL.
+    pub fn lines(&self) -> Vec<&str> {
+        self.stdout
+            .lines()
+            .map(|line| line.trim_start_matches("> "))
+            .filter(|line| !line.is_empty())
+            .collect()
+    }
+}
+
+fn script(source: &str) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let path = env::temp_dir().join(format!(
+        "moon-test-{}-{}.moon",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+    fs::write(&path, source).unwrap();
+    path
+}
+
+fn moon(args: &[&str], path: Option<&PathBuf>) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_moon"));
+    command.args(args);
+    if let Some(path) = path {
+        command.arg(path);
+    }
+    command
+}
+
+/// `moon [prepinace] skript`
+pub fn run(source: &str, flags: &[&str]) -> Run {
+    let path = script(source);
+    let output = moon(flags, Some(&path)).output().unwrap();
+    fs::remove_file(&path).ok();
+    Run::from(output)
+}
+
+/// `moon check skript`
+pub fn check(source: &str) -> Run {
+    let path = script(source);
+    let output = moon(&["check"], Some(&path)).output().unwrap();
+    fs::remove_file(&path).ok();
+    Run::from(output)
+}
+
+/// REPL, kazdy radek `input` se zada zvlast.
+pub fn repl(input: &str, flags: &[&str]) -> Run {
+    let mut child = moon(flags, None)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    Run::from(child.wait_with_output().unwrap())
+}
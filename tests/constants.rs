@@ -0,0 +1,28 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn constants_cannot_be_reassigned() {
+    let result = run("const PI = 3;\nPI = 4;\n", &[]);
+    assert!(result.stderr.contains("[line 2] Error at 'PI': Nelze prirazovat do konstanty 'PI'."), "{}", result.stderr);
+    assert_eq!(result.code, Some(65));
+    let result = run("const PI = 3;\nvar a = 1;\n[PI, a] = [1, 2];\n", &[]);
+    assert!(result.stderr.contains("Nelze prirazovat do konstanty 'PI'."), "{}", result.stderr);
+    let result = run("fun f() {\n    const a = 1;\n    a = 2;\n    return a;\n}\n", &[]);
+    assert!(result.stderr.contains("Nelze prirazovat do konstanty 'a'."), "{}", result.stderr);
+    let result = run("const PI = 3;\nfun f() {\n    return PI;\n}\nprint f();\n", &[]);
+    assert_eq!(result.lines(), ["3"]);
+}
+
+#[test]
+fn sealed_makes_functions_and_classes_constant() {
+    let source = "fun f() {\n    return 1;\n}\nf = 2;\nprint f;\n";
+    let result = run(source, &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.lines(), ["2", "2"]);
+    let result = run(source, &["--sealed"]);
+    assert!(result.stderr.contains("Nelze prirazovat do konstanty 'f'."), "{}", result.stderr);
+    let result = run("class A {}\nA = 1;\n", &["--sealed"]);
+    assert!(result.stderr.contains("Nelze prirazovat do konstanty 'A'."), "{}", result.stderr);
+}
@@ -0,0 +1,31 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn declarations_and_assignments_destructure() {
+    let source = concat!(
+        "var [a, b] = [1, 2];\n",
+        "var [_prvni, ...zbytek] = [1, 2, 3];\n",
+        "print zbytek;\n",
+        "class Bod {\n",
+        "    x = 1;\n",
+        "    y = 2;\n",
+        "}\n",
+        "var {x, y} = Bod();\n",
+        "print x + y;\n",
+        "var _s = [a, b] = [b, a];\n",
+        "print [a, b];\n",
+    );
+    let result = run(source, &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.lines(), ["[2, 3]", "3", "[2, 1]"]);
+}
+
+#[test]
+fn shape_mismatches_are_errors() {
+    let result = run("var [c, d] = [1];\n", &[]);
+    assert!(result.stderr.contains("Ocekavano 2 prvku ale seznam jich ma 1."), "{}", result.stderr);
+    let result = run("var {z} = 5;\n", &[]);
+    assert!(result.stderr.contains("Hodnotu 5 nelze rozlozit podle vlastnosti."), "{}", result.stderr);
+}
@@ -0,0 +1,19 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn variant_constructors_print_their_name() {
+    let result = run("enum Tvar {\n    Kruh(polomer),\n    Bod,\n}\nprint Tvar.Kruh;\nprint Tvar.Kruh(2);\nprint Tvar.Bod;\n", &[]);
+    assert_eq!(result.lines(), ["<variant Tvar.Kruh>", "Tvar.Kruh(2)", "Tvar.Bod"]);
+}
+
+#[test]
+fn variants_destructure_by_field_name() {
+    let source = "enum Tvar {\n    Kruh(polomer),\n    Obdelnik(sirka, vyska),\n}\nvar tvar = Tvar.Obdelnik(2, 3);\nvar {sirka, vyska} = tvar;\nprint sirka * vyska;\nvar Tvar.Obdelnik(s, v) = tvar;\nprint s + v;\nprint match (tvar) {\n    {polomer} => polomer,\n    {sirka} => sirka,\n};\n";
+    let result = run(source, &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.lines(), ["6", "5", "2"]);
+    let result = run("enum Tvar {\n    Kruh(polomer),\n}\nvar {x} = Tvar.Kruh(1);\n", &[]);
+    assert!(result.stderr.contains("nema vlastnost 'x'"), "{}", result.stderr);
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use common::{check, run};
+
+#[test]
+fn defaults_may_use_earlier_parameters() {
+    let source = "fun k(a, b = a) {\n    return a + b;\n}\nprint k(1);\nprint k(1, 5);\nvar l = (a, b = a * 2) => a + b;\nprint l(2);\n";
+    let result = run(source, &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.lines(), ["2", "6", "6"]);
+    assert_eq!(check(source).stderr, "");
+    let result = run("fun k(b = b) {\n    return b;\n}\n", &[]);
+    assert!(result.stderr.contains("Nelze cist lokalni promennou 'b' v jejim vlastnim inicializatoru."), "{}", result.stderr);
+}
+
+
+#[test]
+fn defaults_named_and_rest_parameters() {
+    let source = concat!(
+        "fun f(a, b = 10, ...zbytek) {\n",
+        "    return [a, b, zbytek];\n",
+        "}\n",
+        "print f(1);\n",
+        "print f(1, 2, 3, 4);\n",
+        "print f(b: 2, a: 1);\n",
+        "class Bod {\n",
+        "    init(_x, y = 0) {\n",
+        "        var _b = this.y = y;\n",
+        "    }\n",
+        "}\n",
+        "print Bod(1).y;\n",
+    );
+    let result = run(source, &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.lines(), ["[1, 10, []]", "[1, 2, [3, 4]]", "[1, 2, []]", "0"]);
+}
+
+#[test]
+fn arity_errors_report_the_accepted_range() {
+    let result = run("fun f(a, _b = 1) {\n    return a;\n}\nprint f();\n", &[]);
+    assert!(result.stderr.contains("Ocekavano 1 az 2 argumentu ale bylo zadano 0."), "{}", result.stderr);
+    let result = run("class Bod {\n    init(x) {\n        print x;\n    }\n}\nvar b = Bod(1, 2);\n", &[]);
+    assert!(result.stderr.contains("Ocekavano 1 argumentu ale bylo zadano 2."), "{}", result.stderr);
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn generators_yield_until_exhausted() {
+    let source = concat!(
+        "fun cisla(n) {\n",
+        "    var i = 0;\n",
+        "    while (i < n) {\n",
+        "        yield i;\n",
+        "        var _x = i = i + 1;\n",
+        "    }\n",
+        "    return;\n",
+        "}\n",
+        "for (x in cisla(3)) print x;\n",
+        "var g = cisla(1);\n",
+        "print g.next();\n",
+        "print g.next();\n",
+        "print g.next();\n",
+        "fun prvni(stop) {\n",
+        "    yield 1;\n",
+        "    if (stop) return;\n",
+        "    yield 2;\n",
+        "}\n",
+        "for (x in prvni(true)) print x;\n",
+        "for (x in prvni(false)) print x;\n",
+    );
+    let result = run(source, &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.lines(), ["0", "1", "2", "0", "nil", "nil", "1", "1", "2"]);
+}
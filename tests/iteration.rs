@@ -0,0 +1,33 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn for_in_walks_lists_strings_ranges_and_iterators() {
+    let source = concat!(
+        "for (x in [1, 2]) print x;\n",
+        "for (c in \"ab\") print c;\n",
+        "for (i in 0..2) print i;\n",
+        "for (a, b in [[1, 2], [3, 4]]) print a + b;\n",
+        "class Odpocet {\n",
+        "    n = 2;\n",
+        "    iter() {\n",
+        "        return this;\n",
+        "    }\n",
+        "    next() {\n",
+        "        if (this.n == 0) return nil;\n",
+        "        return this.n = this.n - 1;\n",
+        "    }\n",
+        "}\n",
+        "for (x in Odpocet()) print x;\n",
+    );
+    let result = run(source, &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.lines(), ["1", "2", "\"a\"", "\"b\"", "0", "1", "3", "7", "1", "0"]);
+}
+
+#[test]
+fn for_in_rejects_other_values() {
+    let result = run("for (x in 5) print x;\n", &[]);
+    assert!(result.stderr.contains("Hodnota 5 neni iterovatelna."), "{}", result.stderr);
+}
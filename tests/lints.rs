@@ -0,0 +1,44 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn trailing_allow_comments_cover_only_their_line() {
+    let result = run("fun f(x) {} // moon:allow(unused)\nfun g(y) {}\n", &[]);
+    assert!(!result.stderr.contains("'x'"), "{}", result.stderr);
+    assert!(result.stderr.contains("[line 2] Warning at 'y': Nepouzity parametr 'y'."), "{}", result.stderr);
+    let result = run("// moon:allow(unused)\nfun f(x) {}\nfun g(y) {}\n", &[]);
+    assert!(!result.stderr.contains("'x'"), "{}", result.stderr);
+    assert!(result.stderr.contains("[line 3] Warning at 'y'"), "{}", result.stderr);
+}
+
+#[test]
+fn lint_levels_come_from_the_command_line() {
+    let source = "fun f(x) {\n    return 1;\n}\nprint f(1);\n";
+    let result = run(source, &[]);
+    assert!(result.stderr.contains("[line 1] Warning at 'x': Nepouzity parametr 'x'. (unused)"), "{}", result.stderr);
+    assert_eq!(result.lines(), ["1"]);
+    assert_eq!(result.code, Some(0));
+    let result = run(source, &["--deny=unused"]);
+    assert!(result.stderr.contains("[line 1] Error at 'x': Nepouzity parametr 'x'. (unused)"), "{}", result.stderr);
+    assert_eq!(result.code, Some(65));
+    let result = run(source, &["--allow=all"]);
+    assert_eq!(result.stderr, "");
+    let result = run(source, &["--warn=bogus"]);
+    assert_eq!(result.lines(), ["Nezname varovani 'bogus'."]);
+    assert_eq!(result.code, Some(64));
+}
+
+#[test]
+fn other_lints_are_reported() {
+    let result = run("var a = 1;\nif (a = 2) print a;\nprint 1 == \"1\";\n{\n}\n", &[]);
+    assert!(result.stderr.contains("(assign-in-condition)"), "{}", result.stderr);
+    assert!(result.stderr.contains("Porovnani typu cislo a retezec je vzdy false. (type-comparison)"), "{}", result.stderr);
+    assert!(result.stderr.contains("[line 4] Warning at '{': Prazdny blok. (empty-block)"), "{}", result.stderr);
+    let source = "fun f() {\n    var a = 1;\n    {\n        var a = 2;\n        print a;\n    }\n    return a;\n}\n";
+    assert_eq!(run(source, &[]).stderr, "");
+    let result = run(source, &["--warn=shadowing"]);
+    assert!(result.stderr.contains("[line 4] Warning at 'a': Promenna 'a' zastinuje promennou z nadrazeneho bloku."), "{}", result.stderr);
+    let result = run("class A {\n    f() {\n        return () => this;\n    }\n}\n", &[]);
+    assert!(result.stderr.contains("[line 3] Warning at 'this': Lambda zachycuje 'this' z okolni metody. (captured-this)"), "{}", result.stderr);
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn in_and_not_in_test_membership() {
+    let source = concat!(
+        "print \"vet\" in \"svete\";\n",
+        "print 2 in [1, 2];\n",
+        "print 3 not in (1, 2);\n",
+        "print 1 in #{1};\n",
+        "print 4 in 1..4;\n",
+        "print 4 in 1..=4;\n",
+        "class Sudy {\n",
+        "    __contains__(n) {\n",
+        "        return n % 2 == 0;\n",
+        "    }\n",
+        "}\n",
+        "print 4 in Sudy();\n",
+        "print 3 not in Sudy();\n",
+    );
+    let result = run(source, &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.lines(), ["true", "true", "true", "true", "false", "true", "true", "true"]);
+}
+
+#[test]
+fn in_rejects_values_without_membership() {
+    let result = run("print 1 in 5;\n", &[]);
+    assert!(result.stderr.contains("Operator 'in' lze pouzit jen na"), "{}", result.stderr);
+    assert_eq!(result.code, Some(70));
+}
@@ -0,0 +1,11 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn native_errors_point_at_the_call() {
+    for call in ["int(\"abc\")", "len(1)", "getattr(1, 1)", "set(1)"] {
+        let result = run(&format!("var a = 1;\n\nprint {};\n", call), &[]);
+        assert!(result.stderr.contains("[line 3]"), "{}: {}", call, result.stderr);
+    }
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn integers_equal_floats_only_exactly() {
+    let result = run(
+        "print 2 ** 53 + 1 == 9007199254740992.0;\n\
+         print 2 ** 53 == 9007199254740992.0;\n\
+         print 3 == 3.0;\n\
+         print 3.5 != 3;\n\
+         print 2 ** 80 == 1208925819614629174706176.0;\n",
+        &[],
+    );
+    assert_eq!(result.lines(), ["false", "true", "true", "true", "true"]);
+}
+
+#[test]
+fn huge_powers_fail_instead_of_hanging() {
+    let result = run("print 2 ** 10000000000;\n", &[]);
+    assert!(result.stderr.contains("Vysledek umocneni je prilis velky."), "{}", result.stderr);
+    let result = run("print 1 ** 10000000000;\nprint (-1) ** 10000000001;\nprint 2 ** 100;\n", &[]);
+    assert_eq!(result.lines(), ["1", "-1", "1267650600228229401496703205376"]);
+}
+
+#[test]
+fn big_right_shifts_are_fast_and_floor() {
+    let source = "print (1 << 3000000) >> 3000000;\nprint -7 >> 1;\nprint (-(1 << 100) - 1) >> 99;\nprint -12345678901234567890123 >> 7;\nprint -(1 << 70) >> 4294967296;\n";
+    let start = std::time::Instant::now();
+    let result = run(source, &[]);
+    assert!(start.elapsed().as_secs() < 5);
+    assert_eq!(result.lines(), ["1", "-4", "-3", "-96450616415895061642", "-1"]);
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn optional_chaining_and_coalescing_short_circuit() {
+    let source = concat!(
+        "class Counter {\n",
+        "    n = 0;\n",
+        "}\n",
+        "var calls = Counter();\n",
+        "fun side() {\n",
+        "    var _c = calls.n = calls.n + 1;\n",
+        "    return \"x\";\n",
+        "}\n",
+        "var a = nil;\n",
+        "print a?.field;\n",
+        "print a?.method(side());\n",
+        "print a ?? \"vychozi\";\n",
+        "print 0 ?? side();\n",
+        "print calls.n;\n",
+        "print 1 > 2 ? \"ano\" : \"ne\";\n",
+        "print true ? 1 : false ? 2 : 3;\n",
+        "var c = Counter();\n",
+        "print c?.n;\n",
+    );
+    let result = run(source, &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.lines(), ["nil", "nil", "\"vychozi\"", "0", "0", "\"ne\"", "1", "0"]);
+}
+
+#[test]
+fn plain_property_access_on_nil_still_fails() {
+    let result = run("var a = nil;\nprint a.field;\n", &[]);
+    assert!(result.stderr.contains("Jen instance maji vlastnosti."), "{}", result.stderr);
+}
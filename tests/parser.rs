@@ -0,0 +1,33 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn code_after_a_returning_block_is_unreachable() {
+    let result = run("fun f() {\n    { return 1; }\n    print 2;\n}\n", &[]);
+    assert!(result.stderr.contains("[line 3] Error at 'print': Nedosazitelny kod po 'return'."), "{}", result.stderr);
+    let result = run("fun f(x) {\n    if (x) return 1; else { return 2; }\n    print 3;\n}\n", &[]);
+    assert!(result.stderr.contains("[line 3] Error at 'print'"), "{}", result.stderr);
+    let result = run("fun f(x) {\n    if (x) return 1;\n    print 3;\n}\n", &[]);
+    assert_eq!(result.stderr, "");
+}
+
+#[test]
+fn lambdas_may_refer_to_the_variable_they_initialize() {
+    let result = run("fun f() {\n    var r = (n) => n <= 0 ? 0 : r(n - 1);\n    return r(3);\n}\nprint f();\n", &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.lines(), ["0"]);
+    let result = run("fun g() {\n    var x = x;\n    return x;\n}\n", &[]);
+    assert!(result.stderr.contains("Nelze cist lokalni promennou 'x' v jejim vlastnim inicializatoru."), "{}", result.stderr);
+}
+
+#[test]
+fn locals_cannot_redeclare_parameters() {
+    let result = run("fun f(a) {\n    var a = 1;\n    return a;\n}\n", &[]);
+    assert!(result.stderr.contains("[line 2] Error at 'a': Promenna 'a' uz je v tomto bloku deklarovana."), "{}", result.stderr);
+    assert!(!result.stderr.contains("Nepouzity parametr"), "{}", result.stderr);
+    let result = run("var f = (a) => {\n    var a = 1;\n    return a;\n};\n", &[]);
+    assert!(result.stderr.contains("uz je v tomto bloku deklarovana"), "{}", result.stderr);
+    let result = run("fun g(a) {\n    {\n        var a = 1;\n        print a;\n    }\n    return a;\n}\n", &["--allow=shadowing"]);
+    assert_eq!(result.stderr, "");
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn match_supports_structural_patterns() {
+    let source = concat!(
+        "class Bod {\n",
+        "    x = 0;\n",
+        "    y = 0;\n",
+        "}\n",
+        "var p = Bod();\n",
+        "var _p = p.x = 3;\n",
+        "fun popis(v) {\n",
+        "    return match (v) {\n",
+        "        1 | 2 => \"maly\",\n",
+        "        \"x\" => \"iks\",\n",
+        "        [a, b] => a + b,\n",
+        "        Bod{x, y} => x * 10 + y,\n",
+        "        n if n > 10 => \"velky\",\n",
+        "        _ => \"jiny\",\n",
+        "    };\n",
+        "}\n",
+        "print popis(2);\n",
+        "print popis(\"x\");\n",
+        "print popis([4, 5]);\n",
+        "print popis(p);\n",
+        "print popis(11);\n",
+        "print popis(7);\n",
+    );
+    let result = run(source, &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.lines(), ["\"maly\"", "\"iks\"", "9", "30", "\"velky\"", "\"jiny\""]);
+}
+
+#[test]
+fn match_without_a_matching_arm_fails() {
+    let result = run("match (5) {\n    1 => print \"jedna\";\n}\n", &[]);
+    assert!(result.stderr.contains("Zadna vetev 'match' neodpovida hodnote 5."), "{}", result.stderr);
+}
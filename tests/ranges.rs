@@ -0,0 +1,24 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn ranges_are_lazy_and_slice() {
+    let source = concat!(
+        "var r = 0..1000000000;\n",
+        "print r.contains(999999999);\n",
+        "print r.contains(1000000000);\n",
+        "print (0..=10 step 5).contains(10);\n",
+        "for (i in 0..=10 step 4) print i;\n",
+        "print [1, 2, 3, 4][1..3];\n",
+        "print \"ahoj svete\"[..4];\n",
+        "print 5 in 0..10;\n",
+        "print 0..=10 step 5;\n",
+    );
+    let result = run(source, &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(
+        result.lines(),
+        ["true", "false", "true", "0", "4", "8", "[2, 3]", "\"ahoj\"", "true", "0..=10 step 5"]
+    );
+}
@@ -0,0 +1,16 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn duplicate_record_fields_are_rejected() {
+    let result = run("record P(x, x);\n", &[]);
+    assert!(result.stderr.contains("Pole 'x' uz je v zaznamu deklarovane."), "{}", result.stderr);
+}
+
+#[test]
+fn records_with_lists_cannot_go_into_sets() {
+    let result = run("record P(x, y);\nprint #{P(1, 2)};\nvar s = #{P(1, [2])};\n", &[]);
+    assert_eq!(result.lines(), ["#{P(x: 1, y: 2)}"]);
+    assert!(result.stderr.contains("nelze vlozit do mnoziny"), "{}", result.stderr);
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn reflection_natives_inspect_values_and_classes() {
+    let source = concat!(
+        "class Zvire {\n",
+        "    jmeno = \"x\";\n",
+        "    zvuk() {\n",
+        "        return \"?\";\n",
+        "    }\n",
+        "}\n",
+        "class Pes < Zvire {\n",
+        "    stekej(_hlasite, _kolikrat) {\n",
+        "        return \"haf\";\n",
+        "    }\n",
+        "}\n",
+        "var p = Pes();\n",
+        "print type(p);\n",
+        "print type(1);\n",
+        "print type(1.5);\n",
+        "print type(\"a\");\n",
+        "print type(nil);\n",
+        "print type([]);\n",
+        "print p is Zvire;\n",
+        "print p is Pes;\n",
+        "print Zvire() is Pes;\n",
+        "print fields(p);\n",
+        "print methods(Pes);\n",
+        "print hasattr(p, \"jmeno\");\n",
+        "print hasattr(p, \"vek\");\n",
+        "print getattr(p, \"jmeno\");\n",
+        "var _s = setattr(p, \"vek\", 3);\n",
+        "print p.vek;\n",
+        "print classOf(p) == Pes;\n",
+        "print superclass(Pes) == Zvire;\n",
+        "print superclass(Zvire);\n",
+        "print arity(p.stekej);\n",
+    );
+    let result = run(source, &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(
+        result.lines(),
+        [
+            "\"Pes\"",
+            "\"Int\"",
+            "\"Float\"",
+            "\"String\"",
+            "\"Nil\"",
+            "\"List\"",
+            "true",
+            "true",
+            "false",
+            "[\"jmeno\"]",
+            "[\"stekej\", \"zvuk\"]",
+            "true",
+            "false",
+            "\"x\"",
+            "3",
+            "true",
+            "true",
+            "nil",
+            "2",
+        ]
+    );
+}
+
+#[test]
+fn getattr_of_a_missing_property_fails() {
+    let result = run("class A {}\nprint getattr(A(), \"nic\");\n", &[]);
+    assert!(result.stderr.contains("Undefined property 'nic'."), "{}", result.stderr);
+    assert_eq!(result.code, Some(70));
+}
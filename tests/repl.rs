@@ -0,0 +1,32 @@
+mod common;
+
+use common::repl;
+
+#[test]
+fn sealed_repl_allows_redeclaring_earlier_lines() {
+    let result = repl(
+        "fun f() { return 1; }\n\
+         fun f() { return 2; }\n\
+         print f();\n\
+         const C = 1;\n\
+         const C = 2;\n\
+         print C;\n\
+         class A {}\n\
+         class A {}\n",
+        &["--sealed"],
+    );
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.lines(), ["2", "2"]);
+}
+
+#[test]
+fn sealed_repl_rejects_assignment_and_same_line_redeclaration() {
+    let result = repl("const C = 1;\nC = 2;\n", &["--sealed"]);
+    assert!(result.stderr.contains("Nelze prirazovat do konstanty 'C'."), "{}", result.stderr);
+    let result = repl("fun g() { return 1; } fun g() { return 2; }\n", &["--sealed"]);
+    assert!(
+        result.stderr.contains("Konstanta 'g' uz je v tomto bloku deklarovana."),
+        "{}",
+        result.stderr
+    );
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use common::run;
+
+const CLASSES: &str = "class Spatny {\n    __str__() {\n        return 1 - \"a\";\n    }\n    __eq__(other) {\n        return other < \"a\";\n    }\n    __hash__() {\n        return 1 < \"b\";\n    }\n}\nclass Cislo {\n    __str__() {\n        return 42;\n    }\n}\n";
+
+#[test]
+fn str_errors_are_reported() {
+    let result = run(&format!("{}print Spatny();\n", CLASSES), &[]);
+    assert!(result.stderr.contains("Lze odecist jen 2 cisla."), "{}", result.stderr);
+    let result = run(&format!("{}print [Spatny()];\n", CLASSES), &[]);
+    assert!(result.stderr.contains("Lze odecist jen 2 cisla."), "{}", result.stderr);
+    let result = run(&format!("{}print Cislo();\n", CLASSES), &[]);
+    assert!(result.stderr.contains("Metoda __str__ musi vratit retezec, ne Int."), "{}", result.stderr);
+    assert!(result.stdout.is_empty(), "{}", result.stdout);
+}
+
+#[test]
+fn eq_and_hash_errors_are_reported() {
+    let result = run(&format!("{}print [Spatny()] == [Spatny()];\n", CLASSES), &[]);
+    assert!(result.stderr.contains("Lze porovnat jen 2 cisla."), "{}", result.stderr);
+    let result = run(&format!("{}var s = #{{Spatny()}};\n", CLASSES), &[]);
+    assert!(result.stderr.contains("Lze porovnat jen 2 cisla."), "{}", result.stderr);
+    assert!(result.stderr.contains("[line 9]"), "{}", result.stderr);
+}
+
+#[test]
+fn comparisons_work_with_the_instance_on_either_side() {
+    let source = "class V {\n    x = 0;\n    __lt__(other) {\n        return this.x < other;\n    }\n    __eq__(other) {\n        return this.x == other;\n    }\n}\nfun v(x) {\n    var a = V();\n    var _b = a.x = x;\n    return a;\n}\nprint v(9) > 5;\nprint v(5) > 5;\nprint v(5) <= 5;\nprint v(6) <= 5;\nprint 5 < v(9);\nprint 5 < v(5);\nprint 5 > v(1);\nprint 5 > v(5);\n";
+    let result = run(source, &[]);
+    assert_eq!(result.stderr, "");
+    assert_eq!(result.lines(), ["true", "false", "true", "false", "true", "false", "true", "false"]);
+}
+
+#[test]
+fn sets_respect_eq_and_hash() {
+    let classes = "class V {\n    x = 0;\n    __eq__(other) {\n        return this.x == other.x;\n    }\n}\nclass H < V {\n    __hash__() {\n        return this.x;\n    }\n}\nclass Zly {\n    __eq__(other) {\n        return other < \"a\";\n    }\n    __hash__() {\n        return 1;\n    }\n}\n";
+    let result = run(&format!("{}print len(#{{H(), H()}});\nprint H() in #{{H()}};\n", classes), &[]);
+    assert_eq!(result.lines(), ["1", "true"]);
+    let result = run(&format!("{}var s = #{{V(), V()}};\n", classes), &[]);
+    assert!(result.stderr.contains("nelze vlozit do mnoziny"), "{}", result.stderr);
+    let result = run(&format!("{}var s = #{{Zly(), Zly()}};\n", classes), &[]);
+    assert!(result.stderr.contains("Lze porovnat jen 2 cisla."), "{}", result.stderr);
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn assigning_to_an_annotated_parameter_is_checked() {
+    let source = "fun f(a: Int) {\n    a = \"s\";\n    return a;\n}\nvar x = f(1);\n";
+    let result = run(source, &["--strict-types"]);
+    assert!(result.stderr.contains("Promenna 'a' ma typ Int, ale hodnota je String."), "{}", result.stderr);
+    assert_eq!(run(source, &[]).stderr, "");
+}
+
+#[test]
+fn annotated_fields_are_checked() {
+    let result = run(
+        "class Bod {\n    x: Int = 0;\n}\nvar b = Bod();\nb.x = 5;\nb.x = \"s\";\n",
+        &["--strict-types"],
+    );
+    assert_eq!(result.lines(), ["5"]);
+    assert!(result.stderr.contains("Pole 'x' ma typ Int, ale hodnota je String."), "{}", result.stderr);
+    let result = run("class Bod {\n    x: Int = \"nula\";\n}\nvar b = Bod();\n", &["--strict-types"]);
+    assert!(result.stderr.contains("Pole 'x' ma typ Int"), "{}", result.stderr);
+}
+
+#[test]
+fn wrong_defaults_are_reported_as_defaults() {
+    let source = "fun g(x: Int = \"a\") {\n    return x;\n}\nvar a = g(1);\nvar b = g();\n";
+    let result = run(source, &["--strict-types"]);
+    assert!(
+        result.stderr.contains("Parametr 'x' funkce 'g' ma typ Int, ale vychozi hodnota je String."),
+        "{}",
+        result.stderr
+    );
+    let result = run("fun g(x: Int = 1) {\n    return x;\n}\nvar b = g(\"s\");\n", &["--strict-types"]);
+    assert!(result.stderr.contains("ale argument je String."), "{}", result.stderr);
+}
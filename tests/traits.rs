@@ -0,0 +1,22 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn is_compares_classes_by_identity() {
+    let result = run("class A {}\nvar PrvniA = A;\nclass A {}\nprint PrvniA() is A;\nprint A() is A;\nprint A() is PrvniA;\n", &[]);
+    assert_eq!(result.lines(), ["false", "true", "false"]);
+}
+
+#[test]
+fn conflicting_trait_defaults_must_be_overridden() {
+    let traits = "trait T {\n    f() {\n        return 1;\n    }\n}\ntrait U {\n    f() {\n        return 2;\n    }\n}\n";
+    let result = run(&format!("{}class B with T, U {{\n    f() {{\n        return 3;\n    }}\n}}\nprint B().f();\n", traits), &[]);
+    assert_eq!(result.lines(), ["3"]);
+    let result = run(&format!("{}class C with T, U {{}}\n", traits), &[]);
+    assert!(
+        result.stderr.contains("Trida C dostava metodu 'f' z traitu T i U, musi ji definovat sama."),
+        "{}",
+        result.stderr
+    );
+}